@@ -1,68 +1,101 @@
+use aoc_core::{Input, Param, Solution};
 use anyhow::{Context, Result};
-use itertools::Itertools;
-use log::info;
-use std::fs;
-
-const FILEPATH: &str = "data/day_01/input.txt";
-const TARGET_SUM: i64 = 2020;
-
-pub fn part1() -> Result<i64> {
-    let (x, y) = fs::read_to_string(FILEPATH)?
-        .split('\n')
-        .map(str::parse::<i64>)
-        .collect::<Result<Vec<_>, _>>()?
-        .into_iter()
-        .tuple_combinations()
-        .find(|(x, y)| x + y == TARGET_SUM)
-        .with_context(|| format!("No two elements sum up to {}", TARGET_SUM))?;
-
-    info!("x: {}, y: {}", x, y);
-    info!("x + y = {}", x + y);
-    info!("x * y = {}", x * y);
-
-    Ok(x * y)
+use tracing::debug;
+
+use crate::k_sum::find_k_sum;
+
+const TARGET_SUM: Param = Param::new("target_sum", "2020", "What the entries must add up to");
+
+pub fn parse(input: &Input) -> Result<Vec<i64>> {
+    Ok(input.ints()?)
+}
+
+/// The product of the `k` entries summing to `target`.
+fn k_product(entries: &[i64], k: usize, target: i64) -> Result<i64> {
+    let found = find_k_sum(entries, k, target)
+        .with_context(|| format!("No {} elements sum up to {}", k, target))?;
+    let product = found.iter().product();
+
+    debug!(?found, product, "found the entries");
+
+    Ok(product)
+}
+
+pub fn part1(entries: &[i64], target: i64) -> Result<i64> {
+    k_product(entries, 2, target)
 }
 
-pub fn part2() -> Result<i64> {
-    let (x, y, z) = fs::read_to_string(FILEPATH)?
-        .split('\n')
-        .map(str::parse::<i64>)
-        .collect::<Result<Vec<_>, _>>()?
-        .into_iter()
-        .tuple_combinations()
-        .find(|(x, y, z)| x + y + z == TARGET_SUM)
-        .with_context(|| format!("No three elements sum up to {}", TARGET_SUM))?;
-
-    info!("x: {}, y: {}, z: {}", x, y, z);
-    info!("x + y + z = {}", x + y + z);
-    info!("x * y + z = {}", x * y * z);
-
-    Ok(x * y * z)
+pub fn part2(entries: &[i64], target: i64) -> Result<i64> {
+    k_product(entries, 3, target)
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 1;
+    const PARAMS: &'static [Param] = &[TARGET_SUM];
+
+    /// The entries, and the sum to find among them.
+    type Model = (Vec<i64>, i64);
+    type Answer = i64;
+
+    fn parse(input: &Input) -> Result<(Vec<i64>, i64)> {
+        Ok((parse(input)?, input.param(&TARGET_SUM)?))
+    }
+
+    fn part1((entries, target): &(Vec<i64>, i64)) -> Result<i64> {
+        part1(entries, *target)
+    }
+
+    fn part2((entries, target): &(Vec<i64>, i64)) -> Result<i64> {
+        part2(entries, *target)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::path::Path;
+    use aoc_core::answers::expected;
+    use aoc_core::input::default_path;
+    use aoc_core::testing::puzzle_input;
+    use aoc_core::Part;
 
     use super::*;
 
-    const PART1_RESULT: i64 = 55776;
-    const PART2_RESULT: i64 = 223162626;
-
     #[test]
+    #[ignore = "needs the private puzzle input"]
     fn input_file_exists() {
-        assert!(Path::new(FILEPATH).exists());
+        assert!(default_path(2020, 1).is_ok());
+    }
+
+    #[test]
+    fn test_target_sum() {
+        let input = Input::new("1000\n2000\n20\n1010\n1980");
+        let (entries, target) = Day01::parse(&input).unwrap();
+        assert_eq!(part1(&entries, target).unwrap(), 2000 * 20);
+        let input = input.with_params([("target_sum", "3000")].into_iter().collect());
+        let (entries, target) = Day01::parse(&input).unwrap();
+        assert_eq!(part1(&entries, target).unwrap(), 1000 * 2000);
+        assert_eq!(part2(&entries, target).unwrap(), 1000 * 20 * 1980);
     }
 
     #[test]
     fn check_part1() {
-        let result = part1().unwrap();
-        assert_eq!(result, PART1_RESULT);
+        let Some(input) = puzzle_input(2020, 1) else {
+            return;
+        };
+        let (entries, target) = Day01::parse(&input).unwrap();
+        let result = part1(&entries, target).unwrap();
+        assert_eq!(Some(result.into()), expected(2020, 1, Part::One));
     }
 
     #[test]
     fn check_part2() {
-        let result = part2().unwrap();
-        assert_eq!(result, PART2_RESULT);
+        let Some(input) = puzzle_input(2020, 1) else {
+            return;
+        };
+        let (entries, target) = Day01::parse(&input).unwrap();
+        let result = part2(&entries, target).unwrap();
+        assert_eq!(Some(result.into()), expected(2020, 1, Part::Two));
     }
 }
@@ -1,124 +1,127 @@
-use anyhow::Result;
-use lazy_static::lazy_static;
-use regex::Regex;
-use std::fs;
 use std::path::Path;
-use std::str::FromStr;
-use thiserror::Error;
 
-pub const FILEPATH: &str = "data/day_02/input.txt";
-pub const PASSWORD_ENTRY_REGEX: &str = r"(\d+)-(\d+) (.): (.+)";
+use aoc_core::parse::parse_lines;
+use aoc_core::pattern::AocParse;
+use aoc_core::{Input, Param, Solution};
+use anyhow::Result;
+use tracing::debug;
 
-#[derive(Error, Debug)]
-pub enum PasswordEntryError {
-    #[error("Couldn't match regex to string: {0}")]
-    RegexMatchError(String),
+use policy::{PasswordPolicy, Policies, Report};
 
-    #[error("Parse error: {0}")]
-    ParseCharError(#[from] std::char::ParseCharError),
+pub mod policy;
 
-    #[error("Parse error: {0}")]
-    ParseIntError(#[from] std::num::ParseIntError),
+const POLICIES: Param = Param::new(
+    "policies",
+    "",
+    "A TOML file of the policies of each part, instead of those of the puzzle",
+);
 
-    #[error("Infallible: {0}")]
-    Infallible(#[from] std::convert::Infallible),
-}
-
-#[derive(Clone, Debug, PartialEq)]
+#[derive(AocParse, Clone, Debug, PartialEq)]
+#[aoc(pattern = "{first}-{second} {character}")]
 pub struct PasswordRule {
     character: char,
     first: usize,
     second: usize,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(AocParse, Clone, Debug, PartialEq)]
+#[aoc(pattern = "{rule}: {password}")]
 pub struct PasswordEntry {
+    #[aoc(flatten)]
     rule: PasswordRule,
     password: String,
 }
 
-impl FromStr for PasswordEntry {
-    type Err = PasswordEntryError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        lazy_static! {
-            static ref RE: Regex = Regex::new(PASSWORD_ENTRY_REGEX).unwrap();
-        }
-        RE.captures(s)
-            .ok_or_else(|| PasswordEntryError::RegexMatchError(s.to_string()))
-            .and_then(|cap| {
-                Ok(Self {
-                    rule: PasswordRule {
-                        character: cap[3].parse()?,
-                        first: cap[1].parse()?,
-                        second: cap[2].parse()?,
-                    },
-                    password: cap[4].parse()?,
-                })
-            })
-    }
+pub fn parse(input: &Input) -> Result<Vec<PasswordEntry>> {
+    Ok(parse_lines(input, str::parse)?)
 }
 
-fn password_valid_part1(entry: &PasswordEntry) -> bool {
-    let count = entry.password.matches(entry.rule.character).count();
-    count >= entry.rule.first && count <= entry.rule.second
+/// Checks the entries against `policies`, logging each failure with `-v`.
+pub fn check(entries: &[PasswordEntry], policies: &[Box<dyn PasswordPolicy>]) -> Report {
+    let report = policy::check(entries, policies);
+    for failure in &report.failures {
+        debug!(%failure, "invalid entry");
+    }
+    debug!(
+        valid = report.valid,
+        failures = report.failures.len(),
+        "checked the entries"
+    );
+    report
 }
 
-fn password_valid_part2(entry: &PasswordEntry) -> bool {
-    let mut matches = 0;
-    if entry.password.len() >= entry.rule.first
-        && entry.password.as_bytes()[entry.rule.first - 1] as char == entry.rule.character
-    {
-        matches += 1;
-    };
-    if entry.password.len() >= entry.rule.second
-        && entry.password.as_bytes()[entry.rule.second - 1] as char == entry.rule.character
-    {
-        matches += 1;
-    };
-    matches == 1
+/// The entries valid under the puzzle's policy of part 1.
+pub fn part1(entries: &[PasswordEntry]) -> Result<usize> {
+    Ok(check(entries, &Policies::default().part1).valid)
 }
 
-pub fn part1() -> Result<usize> {
-    let result = fs::read_to_string(Path::new(FILEPATH))?
-        .split('\n')
-        .map(str::parse::<PasswordEntry>)
-        .map(|x| x.unwrap())
-        .filter(|x| password_valid_part1(x))
-        .count();
-    Ok(result)
+/// The entries valid under the puzzle's policy of part 2.
+pub fn part2(entries: &[PasswordEntry]) -> Result<usize> {
+    Ok(check(entries, &Policies::default().part2).valid)
 }
 
-pub fn part2() -> Result<usize> {
-    let result = fs::read_to_string(Path::new(FILEPATH))?
-        .split('\n')
-        .map(str::parse::<PasswordEntry>)
-        .map(|x| x.unwrap())
-        .filter(|x| password_valid_part2(x))
-        .count();
-    Ok(result)
+pub struct Day02;
+
+impl Solution for Day02 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 2;
+    const PARAMS: &'static [Param] = &[POLICIES];
+
+    type Model = (Vec<PasswordEntry>, Policies);
+    type Answer = usize;
+
+    fn parse(input: &Input) -> Result<(Vec<PasswordEntry>, Policies)> {
+        let path: String = input.param(&POLICIES)?;
+        let policies = match path.as_str() {
+            "" => Policies::default(),
+            path => Policies::load(Path::new(path))?,
+        };
+        Ok((parse(input)?, policies))
+    }
+
+    fn part1((entries, policies): &(Vec<PasswordEntry>, Policies)) -> Result<usize> {
+        Ok(check(entries, &policies.part1).valid)
+    }
+
+    fn part2((entries, policies): &(Vec<PasswordEntry>, Policies)) -> Result<usize> {
+        Ok(check(entries, &policies.part2).valid)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use aoc_core::answers::expected;
+    use aoc_core::input::default_path;
+    use aoc_core::testing::puzzle_input;
+    use aoc_core::{ParseError, Part};
+
+    use super::policy::{CountRange, PositionalXor};
     use super::*;
 
     const TEST_PASSWORD_ENTRY_STR: &str = "1-3 m: mmmaoou";
     const BAD_PASSWORD_ENTRY_STR: &str = "1-3 m mmmaoou";
 
-    const PART1_RESULT: usize = 524;
-    const PART2_RESULT: usize = 485;
-
     #[test]
+    #[ignore = "needs the private puzzle input"]
     fn input_file_exists() {
-        assert!(Path::new(FILEPATH).exists());
+        assert!(default_path(2020, 2).is_ok());
     }
 
     #[test]
-    fn correct_regex() {
-        let re = Regex::new(PASSWORD_ENTRY_REGEX).unwrap();
-        assert!(re.captures(TEST_PASSWORD_ENTRY_STR).is_some());
-        assert!(re.captures(BAD_PASSWORD_ENTRY_STR).is_none());
+    fn correct_rule_parsing() {
+        assert_eq!(
+            str::parse::<PasswordRule>("2-9 c").unwrap(),
+            PasswordRule {
+                character: 'c',
+                first: 2,
+                second: 9,
+            }
+        );
+        let err = str::parse::<PasswordRule>("2-9 cd").unwrap_err();
+        assert_eq!(
+            (err.column, err.message.as_str()),
+            (5, "invalid character: too many characters in string")
+        );
     }
 
     #[test]
@@ -134,15 +137,45 @@ mod tests {
                 password: "mmmaoou".to_string(),
             }
         );
-        match str::parse::<PasswordEntry>(BAD_PASSWORD_ENTRY_STR) {
-            Err(PasswordEntryError::RegexMatchError(_s)) => (),
-            _ => panic!("Expected RegexMatchError"),
-        }
+        let err = str::parse::<PasswordEntry>(BAD_PASSWORD_ENTRY_STR).unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.message.as_str()),
+            (1, 1, "expected \": \"")
+        );
+        assert_eq!(err.text, BAD_PASSWORD_ENTRY_STR);
+        assert_eq!(
+            str::parse::<PasswordEntry>("1-3 a: b: c").unwrap().password,
+            "b: c"
+        );
+        let err = str::parse::<PasswordEntry>("1-x a: abc").unwrap_err();
+        assert_eq!(
+            (err.column, err.message.as_str()),
+            (3, "invalid second: invalid digit found in string")
+        );
+    }
+
+    #[test]
+    fn parse_error_location() {
+        let err = parse(&Input::new("1-3 a: abcde\n1-99999999999999999999 b: cdefg")).unwrap_err();
+        let err = err.downcast::<ParseError>().unwrap();
+        assert_eq!((err.line, err.column), (2, 3));
+    }
+
+    #[test]
+    fn test_check() {
+        let entries = parse(&Input::new("1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc")).unwrap();
+        let report = check(&entries, &Policies::default().part1);
+        assert_eq!(report.valid, 2);
+        assert_eq!(
+            report.failures[0].to_string(),
+            "line 2: count_range: 'b' appears 0 times, expected 1 to 3"
+        );
+        assert_eq!((part1(&entries).unwrap(), part2(&entries).unwrap()), (2, 1));
     }
 
     #[test]
     fn check_password_valid_part1() {
-        assert!(password_valid_part1(&PasswordEntry {
+        assert!(CountRange.allows(&PasswordEntry {
             rule: PasswordRule {
                 character: 'm',
                 first: 1,
@@ -150,7 +183,7 @@ mod tests {
             },
             password: "momomoooo".to_string(),
         }));
-        assert!(!password_valid_part1(&PasswordEntry {
+        assert!(!CountRange.allows(&PasswordEntry {
             rule: PasswordRule {
                 character: 'm',
                 first: 1,
@@ -158,7 +191,7 @@ mod tests {
             },
             password: "nononoooo".to_string(),
         }));
-        assert!(!password_valid_part1(&PasswordEntry {
+        assert!(!CountRange.allows(&PasswordEntry {
             rule: PasswordRule {
                 character: 'm',
                 first: 1,
@@ -170,7 +203,7 @@ mod tests {
 
     #[test]
     fn check_password_valid_part2() {
-        assert!(password_valid_part2(&PasswordEntry {
+        assert!(PositionalXor.allows(&PasswordEntry {
             rule: PasswordRule {
                 character: 'm',
                 first: 1,
@@ -178,7 +211,7 @@ mod tests {
             },
             password: "moooooo".to_string(),
         }));
-        assert!(password_valid_part2(&PasswordEntry {
+        assert!(PositionalXor.allows(&PasswordEntry {
             rule: PasswordRule {
                 character: 'm',
                 first: 1,
@@ -186,7 +219,7 @@ mod tests {
             },
             password: "oomoooo".to_string(),
         }));
-        assert!(!password_valid_part2(&PasswordEntry {
+        assert!(!PositionalXor.allows(&PasswordEntry {
             rule: PasswordRule {
                 character: 'm',
                 first: 1,
@@ -194,7 +227,7 @@ mod tests {
             },
             password: "momooooo".to_string(),
         }));
-        assert!(!password_valid_part2(&PasswordEntry {
+        assert!(!PositionalXor.allows(&PasswordEntry {
             rule: PasswordRule {
                 character: 'm',
                 first: 1,
@@ -206,11 +239,23 @@ mod tests {
 
     #[test]
     fn check_part1() {
-        assert_eq!(part1().unwrap(), PART1_RESULT);
+        let Some(input) = puzzle_input(2020, 2) else {
+            return;
+        };
+        assert_eq!(
+            Some(part1(&parse(&input).unwrap()).unwrap().into()),
+            expected(2020, 2, Part::One)
+        );
     }
 
     #[test]
     fn check_part2() {
-        assert_eq!(part2().unwrap(), PART2_RESULT);
+        let Some(input) = puzzle_input(2020, 2) else {
+            return;
+        };
+        assert_eq!(
+            Some(part2(&parse(&input).unwrap()).unwrap().into()),
+            expected(2020, 2, Part::Two)
+        );
     }
 }
@@ -0,0 +1,302 @@
+use std::fmt::{self, Display};
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::Deserialize;
+
+use super::PasswordEntry;
+
+/// A rule a password entry must follow.
+pub trait PasswordPolicy {
+    /// Name of the policy in reports, e.g. `count_range`.
+    fn name(&self) -> String;
+
+    /// Why `entry` breaks the policy, or `None` if it follows it.
+    fn violation(&self, entry: &PasswordEntry) -> Option<String>;
+
+    fn allows(&self, entry: &PasswordEntry) -> bool {
+        self.violation(entry).is_none()
+    }
+}
+
+/// The character of the rule appears between `first` and `second` times.
+pub struct CountRange;
+
+impl PasswordPolicy for CountRange {
+    fn name(&self) -> String {
+        "count_range".to_string()
+    }
+
+    fn violation(&self, entry: &PasswordEntry) -> Option<String> {
+        let rule = &entry.rule;
+        let count = entry.password.matches(rule.character).count();
+        (count < rule.first || count > rule.second).then(|| {
+            format!(
+                "{:?} appears {} times, expected {} to {}",
+                rule.character, count, rule.first, rule.second
+            )
+        })
+    }
+}
+
+/// The character of the rule is at exactly one of the 1-based positions `first` and `second`.
+pub struct PositionalXor;
+
+impl PositionalXor {
+    fn at(entry: &PasswordEntry, position: usize) -> bool {
+        position
+            .checked_sub(1)
+            .and_then(|i| entry.password.chars().nth(i))
+            == Some(entry.rule.character)
+    }
+}
+
+impl PasswordPolicy for PositionalXor {
+    fn name(&self) -> String {
+        "positional_xor".to_string()
+    }
+
+    fn violation(&self, entry: &PasswordEntry) -> Option<String> {
+        let rule = &entry.rule;
+        let (first, second) = (Self::at(entry, rule.first), Self::at(entry, rule.second));
+        let found = match (first, second) {
+            (true, true) => "both",
+            (false, false) => "neither",
+            _ => return None,
+        };
+        Some(format!(
+            "{:?} is at {} of positions {} and {}, expected exactly one",
+            rule.character, found, rule.first, rule.second
+        ))
+    }
+}
+
+/// The password matches a regular expression.
+pub struct Matches(pub Regex);
+
+impl PasswordPolicy for Matches {
+    fn name(&self) -> String {
+        format!("regex {:?}", self.0.as_str())
+    }
+
+    fn violation(&self, entry: &PasswordEntry) -> Option<String> {
+        (!self.0.is_match(&entry.password))
+            .then(|| format!("{:?} does not match {:?}", entry.password, self.0.as_str()))
+    }
+}
+
+/// The password has at least this many characters.
+pub struct MinLength(pub usize);
+
+impl PasswordPolicy for MinLength {
+    fn name(&self) -> String {
+        format!("min_length {}", self.0)
+    }
+
+    fn violation(&self, entry: &PasswordEntry) -> Option<String> {
+        let length = entry.password.chars().count();
+        (length < self.0).then(|| format!("{} characters, expected at least {}", length, self.0))
+    }
+}
+
+/// A policy as written in a policies file.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub enum PolicyConfig {
+    CountRange,
+    PositionalXor,
+    Regex { pattern: String },
+    MinLength { length: usize },
+}
+
+impl PolicyConfig {
+    pub fn build(&self) -> Result<Box<dyn PasswordPolicy>> {
+        Ok(match self {
+            PolicyConfig::CountRange => Box::new(CountRange),
+            PolicyConfig::PositionalXor => Box::new(PositionalXor),
+            PolicyConfig::Regex { pattern } => Box::new(Matches(
+                Regex::new(pattern).with_context(|| format!("Invalid regex {:?}", pattern))?,
+            )),
+            PolicyConfig::MinLength { length } => Box::new(MinLength(*length)),
+        })
+    }
+}
+
+/// The policies an entry must all follow to count towards each part.
+pub struct Policies {
+    pub part1: Vec<Box<dyn PasswordPolicy>>,
+    pub part2: Vec<Box<dyn PasswordPolicy>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PoliciesFile {
+    #[serde(default)]
+    part1: Vec<PolicyConfig>,
+    #[serde(default)]
+    part2: Vec<PolicyConfig>,
+}
+
+impl Default for Policies {
+    /// The policies of the puzzle.
+    fn default() -> Self {
+        Self {
+            part1: vec![Box::new(CountRange)],
+            part2: vec![Box::new(PositionalXor)],
+        }
+    }
+}
+
+impl Policies {
+    /// Reads policies written as TOML, such as
+    ///
+    /// ```toml
+    /// part1 = [{ kind = "count_range" }, { kind = "min_length", length = 8 }]
+    /// part2 = [{ kind = "positional_xor" }, { kind = "regex", pattern = "^[a-z]+$" }]
+    /// ```
+    pub fn parse(s: &str) -> Result<Self> {
+        let file: PoliciesFile = toml::from_str(s)?;
+        let build = |configs: &[PolicyConfig]| -> Result<Vec<_>> {
+            configs.iter().map(PolicyConfig::build).collect()
+        };
+        Ok(Self {
+            part1: build(&file.part1)?,
+            part2: build(&file.part2)?,
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("Invalid policies file {}", path.display()))
+    }
+}
+
+/// An entry breaking a policy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Failure {
+    /// 1-based position of the entry in the entries checked, which is its line in the input when
+    /// they are all the entries parsed from it.
+    pub line: usize,
+    pub policy: String,
+    pub reason: String,
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}: {}", self.line, self.policy, self.reason)
+    }
+}
+
+/// The outcome of checking entries against policies.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Report {
+    /// The entries following every policy.
+    pub valid: usize,
+    /// Every policy the other entries break, in input order.
+    pub failures: Vec<Failure>,
+}
+
+/// Checks every entry against every one of `policies`.
+pub fn check(entries: &[PasswordEntry], policies: &[Box<dyn PasswordPolicy>]) -> Report {
+    let failures = failures(entries, policies);
+    let mut invalid: Vec<usize> = failures.iter().map(|f| f.line).collect();
+    invalid.dedup();
+    Report {
+        valid: entries.len() - invalid.len(),
+        failures,
+    }
+}
+
+/// Every policy each entry breaks, in input order.
+pub fn failures(entries: &[PasswordEntry], policies: &[Box<dyn PasswordPolicy>]) -> Vec<Failure> {
+    entries
+        .iter()
+        .enumerate()
+        .flat_map(|(i, entry)| {
+            policies.iter().filter_map(move |policy| {
+                policy.violation(entry).map(|reason| Failure {
+                    line: i + 1,
+                    policy: policy.name(),
+                    reason,
+                })
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn entry(s: &str) -> PasswordEntry {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_positional_xor() {
+        assert!(PositionalXor.allows(&entry("1-3 a: abcde")));
+        assert!(!PositionalXor.allows(&entry("2-9 c: ccccccccc")));
+        assert!(PositionalXor.allows(&entry("0-2 b: abc")));
+        assert!(!PositionalXor.allows(&entry("0-0 a: abc")));
+        assert!(PositionalXor.allows(&entry("2-3 é: aébé")));
+        assert_eq!(
+            PositionalXor.violation(&entry("1-3 b: cdefg")).unwrap(),
+            "'b' is at neither of positions 1 and 3, expected exactly one"
+        );
+    }
+
+    #[test]
+    fn test_other_policies() {
+        assert_eq!(
+            CountRange.violation(&entry("1-3 b: cdefg")).unwrap(),
+            "'b' appears 0 times, expected 1 to 3"
+        );
+        assert!(CountRange.allows(&entry("1-2 é: éa")));
+        let lower = Matches(Regex::new("^[a-z]+$").unwrap());
+        assert!(lower.allows(&entry("1-3 a: abcde")));
+        assert!(!lower.allows(&entry("1-3 a: abc1")));
+        assert!(MinLength(4).allows(&entry("1-3 a: éééé")));
+        assert!(!MinLength(4).allows(&entry("1-3 a: abc")));
+    }
+
+    #[test]
+    fn test_policies_file() {
+        let policies = Policies::parse(
+            r#"
+            part1 = [{ kind = "count_range" }, { kind = "min_length", length = 8 }]
+            part2 = [{ kind = "regex", pattern = "^[a-z]+$" }]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(policies.part1.len(), 2);
+        assert_eq!(policies.part2[0].name(), "regex \"^[a-z]+$\"");
+        assert!(Policies::parse("part1 = [{ kind = \"regex\", pattern = \"(\" }]").is_err());
+        assert!(Policies::parse("part1 = [{ kind = \"longest\" }]").is_err());
+        assert!(Policies::parse("part3 = []").is_err());
+    }
+
+    #[test]
+    fn test_failures() {
+        let entries = ["1-3 a: abcde", "1-3 b: cdefg", "2-9 c: ccccccccc"].map(entry);
+        let policies: Vec<Box<dyn PasswordPolicy>> =
+            vec![Box::new(CountRange), Box::new(MinLength(6))];
+        let report: Vec<String> = failures(&entries, &policies)
+            .iter()
+            .map(Failure::to_string)
+            .collect();
+        assert_eq!(
+            report,
+            vec![
+                "line 1: min_length 6: 5 characters, expected at least 6",
+                "line 2: count_range: 'b' appears 0 times, expected 1 to 3",
+                "line 2: min_length 6: 5 characters, expected at least 6",
+            ]
+        );
+        let report = check(&entries, &policies);
+        assert_eq!(report.valid, 1);
+        assert_eq!(report.failures.len(), 3);
+    }
+}
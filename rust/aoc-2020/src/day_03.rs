@@ -1,59 +1,241 @@
-use anyhow::Result;
-use std::fs;
-
-pub const FILEPATH: &str = "data/day_03/input.txt";
-const TREE: char = '#';
-const SLOPES: &[(usize, usize)] = &[(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
-
-pub fn part1() -> Result<usize> {
-    Ok(fs::read_to_string(FILEPATH)?
-        .split('\n')
-        .fold((0usize, 0usize), |(n, pos), s| {
-            (
-                n + (s.chars().nth(pos % s.len()).unwrap() == TREE) as usize,
-                pos + 3,
+use aoc_core::pattern::AocParse;
+use aoc_core::{Answer, Input, Param, ParseError, Solution};
+use anyhow::{bail, Context, Result};
+use tracing::{info, trace};
+
+use terrain::{Goal, Terrain};
+
+pub mod terrain;
+
+const OPEN: u8 = b'.';
+const TREE: Param = Param::new("tree", "#", "The cell marking a tree");
+const SLOPES: Param = Param::new(
+    "slopes",
+    "1,1 3,1 5,1 7,1 1,2",
+    "The slopes part 2 multiplies the trees of, as right,down",
+);
+const SEARCH: Param = Param::new(
+    "search",
+    "",
+    "Makes part 1 search for the slope hitting the fewest or most trees within bounds, as \
+     `most 1..=7,1..=2` for right and down",
+);
+
+/// How far the toboggan goes right for each step down.
+#[derive(AocParse, Clone, Copy, Debug, PartialEq)]
+#[aoc(pattern = "{right},{down}")]
+pub struct Slope {
+    pub right: usize,
+    pub down: usize,
+}
+
+/// A slope search of part 1, as set by the `search` parameter.
+#[derive(AocParse, Clone, Copy, Debug, PartialEq)]
+#[aoc(pattern = "{goal} {right_low}..={right_high},{down_low}..={down_high}")]
+pub struct Search {
+    pub goal: Goal,
+    pub right_low: usize,
+    pub right_high: usize,
+    pub down_low: usize,
+    pub down_high: usize,
+}
+
+fn parse_terrain(input: &Input, tree: u8) -> Result<Vec<&[u8]>, ParseError> {
+    for line in input.lines() {
+        if let Some(i) = line.bytes().position(|c| c != tree && c != OPEN) {
+            return Err(input.error(&line[i..], format!("expected '.' or '{}'", tree as char)));
+        }
+    }
+    input.grid()
+}
+
+pub fn parse(input: &Input) -> Result<Terrain> {
+    let tree: char = input.param(&TREE)?;
+    if !tree.is_ascii() || tree as u8 == OPEN {
+        bail!(
+            "Invalid parameter tree={:?}, expected an ASCII character other than '.'",
+            tree
+        );
+    }
+    let tree = tree as u8;
+    Terrain::new(
+        parse_terrain(input, tree)?
+            .into_iter()
+            .map(|row| row.iter().map(move |&c| c == tree)),
+    )
+}
+
+fn parse_slopes(input: &Input) -> Result<Vec<Slope>> {
+    let slopes: String = input.param(&SLOPES)?;
+    let context = || format!("Invalid parameter slopes={:?}", slopes);
+    let slopes = slopes
+        .split_whitespace()
+        .map(str::parse)
+        .collect::<Result<Vec<Slope>, _>>()
+        .with_context(context)?;
+    if slopes.iter().any(|s| s.down == 0) {
+        bail!("{}: slopes must go down", context());
+    }
+    Ok(slopes)
+}
+
+fn parse_search(input: &Input) -> Result<Option<Search>> {
+    let search: String = input.param(&SEARCH)?;
+    if search.is_empty() {
+        return Ok(None);
+    }
+    let parsed = search
+        .parse()
+        .with_context(|| format!("Invalid parameter search={:?}", search))?;
+    Ok(Some(parsed))
+}
+
+pub fn part1(terrain: &Terrain) -> Result<usize> {
+    let path: Vec<_> = terrain.path(Slope { right: 3, down: 1 }).collect();
+    trace!("path:\n{}", terrain.render(&path));
+    Ok(path.iter().filter(|visit| visit.tree).count())
+}
+
+pub fn part2(terrain: &Terrain, slopes: &[Slope]) -> Result<u128> {
+    Ok(terrain
+        .trees_by_slope(slopes)
+        .into_iter()
+        .map(|trees| trees as u128)
+        .product())
+}
+
+/// The slope `search` looks for, with the trees it hits.
+pub fn search(terrain: &Terrain, search: &Search) -> Result<(Slope, usize)> {
+    terrain
+        .find_slope(
+            search.right_low..=search.right_high,
+            search.down_low..=search.down_high,
+            search.goal,
+        )
+        .with_context(|| {
+            format!(
+                "No slope goes down with down in {}..={}",
+                search.down_low, search.down_high
             )
         })
-        .0)
-}
-
-pub fn part2() -> Result<usize> {
-    let content = fs::read_to_string(FILEPATH)?;
-    let terrain = content.split('\n').collect::<Vec<&str>>();
-    Ok(SLOPES
-        .iter()
-        .map(|&(r, d)| {
-            terrain
-                .iter()
-                .step_by(d)
-                .fold((0usize, 0usize), |(n, pos), s| {
-                    (
-                        n + (s.chars().nth(pos % s.len()).unwrap() == TREE) as usize,
-                        pos + r,
-                    )
-                })
-                .0
-        })
-        .product())
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 3;
+    const PARAMS: &'static [Param] = &[TREE, SLOPES, SEARCH];
+
+    /// The terrain, the slopes of part 2, and the slope search of part 1 if any.
+    type Model = (Terrain, Vec<Slope>, Option<Search>);
+    type Answer = Answer;
+
+    fn parse(input: &Input) -> Result<(Terrain, Vec<Slope>, Option<Search>)> {
+        Ok((parse(input)?, parse_slopes(input)?, parse_search(input)?))
+    }
+
+    fn part1((terrain, _, search_for): &(Terrain, Vec<Slope>, Option<Search>)) -> Result<Answer> {
+        let Some(search_for) = search_for else {
+            return Ok(part1(terrain)?.into());
+        };
+        let (slope, trees) = search(terrain, search_for)?;
+        info!("slope {},{} hits {} trees", slope.right, slope.down, trees);
+        Ok(trees.into())
+    }
+
+    fn part2((terrain, slopes, _): &(Terrain, Vec<Slope>, Option<Search>)) -> Result<Answer> {
+        Ok(part2(terrain, slopes)?.into())
+    }
 }
 
 #[cfg(test)]
 mod test {
+    use aoc_core::answers::expected;
+    use aoc_core::testing::puzzle_input;
+    use aoc_core::Part;
 
     use super::*;
 
-    const PART1_RESULT: usize = 162;
-    const PART2_RESULT: usize = 3064612320;
+    #[test]
+    fn test_parse_terrain() {
+        assert_eq!(
+            parse_terrain(&Input::new("..#\n#..\n"), b'#').unwrap(),
+            vec![b"..#", b"#.."]
+        );
+        let err = parse_terrain(&Input::new("..#\n#.X."), b'#').unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        let err = parse_terrain(&Input::new("..#\n\n#.."), b'#').unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+
+    #[test]
+    fn test_params() {
+        let input = Input::new("@..\n.@.\n..@")
+            .with_params([("tree", "@"), ("slopes", "1,1 2,2")].into_iter().collect());
+        let (terrain, slopes, search_for) = Day03::parse(&input).unwrap();
+        assert!(terrain.is_tree(0, 0) && !terrain.is_tree(0, 1));
+        assert_eq!(
+            slopes,
+            vec![Slope { right: 1, down: 1 }, Slope { right: 2, down: 2 }]
+        );
+        assert_eq!(part2(&terrain, &slopes).unwrap(), 3 * 2);
+        assert_eq!(search_for, None);
+
+        let input = Input::new("..#").with_params([("slopes", "1,0")].into_iter().collect());
+        assert_eq!(
+            Day03::parse(&input).unwrap_err().to_string(),
+            "Invalid parameter slopes=\"1,0\": slopes must go down"
+        );
+        let input = Input::new("..#").with_params([("tree", ".")].into_iter().collect());
+        assert!(Day03::parse(&input).is_err());
+    }
+
+    #[test]
+    fn test_search() {
+        let input = Input::new("#.#\n.#.\n#.#")
+            .with_params([("search", "most 0..=2,1..=2")].into_iter().collect());
+        let model = Day03::parse(&input).unwrap();
+        assert_eq!(
+            model.2,
+            Some(Search {
+                goal: Goal::MostTrees,
+                right_low: 0,
+                right_high: 2,
+                down_low: 1,
+                down_high: 2,
+            })
+        );
+        assert_eq!(
+            search(&model.0, &model.2.unwrap()).unwrap(),
+            (Slope { right: 1, down: 1 }, 3)
+        );
+        assert_eq!(Day03::part1(&model).unwrap(), Answer::UInt(3));
+
+        let input =
+            Input::new("#.#").with_params([("search", "fewest 0..=2,0..=0")].into_iter().collect());
+        let model = Day03::parse(&input).unwrap();
+        assert!(Day03::part1(&model).is_err());
+        let input =
+            Input::new("#.#").with_params([("search", "some 0..=2,1..=1")].into_iter().collect());
+        assert!(Day03::parse(&input).is_err());
+    }
 
     #[test]
     fn check_part1() {
-        let result = part1().unwrap();
-        assert_eq!(result, PART1_RESULT);
+        let Some(input) = puzzle_input(2020, 3) else {
+            return;
+        };
+        let result = part1(&parse(&input).unwrap()).unwrap();
+        assert_eq!(Some(result.into()), expected(2020, 3, Part::One));
     }
 
     #[test]
     fn check_part2() {
-        let result = part2().unwrap();
-        assert_eq!(result, PART2_RESULT);
+        let Some(input) = puzzle_input(2020, 3) else {
+            return;
+        };
+        let result = part2(&parse(&input).unwrap(), &parse_slopes(&input).unwrap()).unwrap();
+        assert_eq!(Some(result.into()), expected(2020, 3, Part::Two));
     }
 }
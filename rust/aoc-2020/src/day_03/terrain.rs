@@ -0,0 +1,310 @@
+use std::cmp::Reverse;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use anyhow::{bail, Error, Result};
+
+use super::Slope;
+
+/// A map of open squares and trees, repeating to the right forever.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Terrain {
+    width: usize,
+    /// Row-major, `true` where there is a tree.
+    trees: Vec<bool>,
+}
+
+/// Which slopes a search looks for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Goal {
+    FewestTrees,
+    MostTrees,
+}
+
+impl FromStr for Goal {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "fewest" => Ok(Goal::FewestTrees),
+            "most" => Ok(Goal::MostTrees),
+            _ => bail!("expected 'fewest' or 'most', got {:?}", s),
+        }
+    }
+}
+
+/// A square the toboggan stops on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Visit {
+    pub row: usize,
+    /// Distance from the left edge, not wrapped to the width of the map.
+    pub column: usize,
+    pub tree: bool,
+}
+
+impl Terrain {
+    /// Builds the terrain from rows of the same, non-zero width.
+    pub fn new<R: IntoIterator<Item = bool>>(rows: impl IntoIterator<Item = R>) -> Result<Self> {
+        let mut width = None;
+        let mut trees = vec![];
+        for (i, row) in rows.into_iter().enumerate() {
+            let start = trees.len();
+            trees.extend(row);
+            let row_width = trees.len() - start;
+            if row_width == 0 {
+                bail!("Row {} is empty, expected a width greater than 0", i + 1);
+            }
+            let width = *width.get_or_insert(row_width);
+            if row_width != width {
+                bail!(
+                    "Row {} has {} squares, expected {}",
+                    i + 1,
+                    row_width,
+                    width
+                );
+            }
+        }
+        Ok(Self {
+            width: width.unwrap_or(0),
+            trees,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.trees.len().checked_div(self.width).unwrap_or(0)
+    }
+
+    /// Whether there is a tree at `row`, `column` columns right of the left edge.
+    pub fn is_tree(&self, row: usize, column: usize) -> bool {
+        self.trees[row * self.width + column % self.width]
+    }
+
+    /// The squares the toboggan stops on going down `slope` from the top-left corner, until it
+    /// leaves the bottom of the map, or its column no longer fits a `usize`. Panics if the slope
+    /// does not go down.
+    pub fn path(&self, slope: Slope) -> impl Iterator<Item = Visit> + '_ {
+        (0..self.height())
+            .step_by(slope.down)
+            .enumerate()
+            .map_while(move |(step, row)| {
+                let column = step.checked_mul(slope.right)?;
+                Some(Visit {
+                    row,
+                    column,
+                    tree: self.is_tree(row, column),
+                })
+            })
+    }
+
+    /// The trees hit going down `slope`, however far right it goes. Panics if the slope does not
+    /// go down.
+    pub fn trees(&self, slope: Slope) -> usize {
+        self.trees_by_slope(&[slope])[0]
+    }
+
+    /// The trees hit going down each of `slopes`, counted in a single pass over the rows. Panics
+    /// if a slope does not go down.
+    pub fn trees_by_slope(&self, slopes: &[Slope]) -> Vec<usize> {
+        let mut counts = vec![0; slopes.len()];
+        if self.width == 0 {
+            return counts;
+        }
+        // Columns are kept wrapped, so long slopes cannot overflow.
+        let steps: Vec<_> = slopes.iter().map(|s| s.right % self.width).collect();
+        let mut columns = vec![0; slopes.len()];
+        for (row, squares) in self.trees.chunks(self.width).enumerate() {
+            for (i, slope) in slopes.iter().enumerate() {
+                if row % slope.down == 0 {
+                    counts[i] += squares[columns[i]] as usize;
+                    columns[i] = (columns[i] + steps[i]) % self.width;
+                }
+            }
+        }
+        counts
+    }
+
+    /// The slope going `right` and `down` within the bounds that hits the fewest or most trees,
+    /// with its trees. Ties go to the slope going down least, then right least. Slopes that do not
+    /// go down are skipped.
+    pub fn find_slope(
+        &self,
+        right: RangeInclusive<usize>,
+        down: RangeInclusive<usize>,
+        goal: Goal,
+    ) -> Option<(Slope, usize)> {
+        let slopes: Vec<_> = down
+            .filter(|&down| down > 0)
+            .flat_map(|down| right.clone().map(move |right| Slope { right, down }))
+            .collect();
+        let counts = self.trees_by_slope(&slopes);
+        let candidates = slopes.into_iter().zip(counts).enumerate();
+        let (_, best) = match goal {
+            Goal::FewestTrees => candidates.min_by_key(|&(i, (_, trees))| (trees, i)),
+            Goal::MostTrees => candidates.max_by_key(|&(i, (_, trees))| (trees, Reverse(i))),
+        }?;
+        Some(best)
+    }
+
+    /// Draws the terrain repeated as far right as `path` goes, with `O` on the open squares and
+    /// `X` on the trees it stops on, as in the puzzle statement.
+    pub fn render(&self, path: &[Visit]) -> String {
+        let columns = path
+            .iter()
+            .map(|v| v.column / self.width + 1)
+            .max()
+            .unwrap_or(1)
+            * self.width;
+        let mut stops = vec![None; self.height()];
+        for visit in path {
+            stops[visit.row] = Some(visit.column);
+        }
+        let mut out = String::with_capacity((columns + 1) * self.height());
+        for (row, stop) in stops.into_iter().enumerate() {
+            for column in 0..columns {
+                out.push(match (stop == Some(column), self.is_tree(row, column)) {
+                    (true, true) => 'X',
+                    (true, false) => 'O',
+                    (false, true) => '#',
+                    (false, false) => '.',
+                });
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_core::testing::Rng;
+
+    use super::*;
+
+    const EXAMPLE: &str = "..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#";
+
+    fn example() -> Terrain {
+        Terrain::new(EXAMPLE.lines().map(|line| line.chars().map(|c| c == '#'))).unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        let terrain = example();
+        assert_eq!((terrain.width(), terrain.height()), (11, 11));
+        assert!(terrain.is_tree(0, 2));
+        assert!(terrain.is_tree(0, 13));
+        assert!(!terrain.is_tree(0, 11));
+        assert!(Terrain::new([vec![true], vec![true, false]]).is_err());
+        assert_eq!(
+            Terrain::new([vec![true], vec![]]).unwrap_err().to_string(),
+            "Row 2 is empty, expected a width greater than 0"
+        );
+        assert_eq!(
+            Terrain::new([vec![true], vec![true, false]])
+                .unwrap_err()
+                .to_string(),
+            "Row 2 has 2 squares, expected 1"
+        );
+        assert_eq!(Terrain::new(Vec::<Vec<bool>>::new()).unwrap().height(), 0);
+    }
+
+    #[test]
+    fn test_path() {
+        let terrain = example();
+        let path: Vec<_> = terrain.path(Slope { right: 1, down: 2 }).collect();
+        assert_eq!(path.len(), 6);
+        assert_eq!(
+            path[1],
+            Visit {
+                row: 2,
+                column: 1,
+                tree: true
+            }
+        );
+        assert_eq!(path.iter().filter(|v| v.tree).count(), 2);
+        let counts: Vec<_> = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)]
+            .map(|(right, down)| terrain.trees(Slope { right, down }))
+            .to_vec();
+        assert_eq!(counts, vec![2, 7, 3, 4, 2]);
+
+        let far = Slope {
+            right: usize::MAX,
+            down: 1,
+        };
+        assert_eq!(terrain.path(far).count(), 2);
+        let wrapped = Slope {
+            right: usize::MAX % 11,
+            down: 1,
+        };
+        assert_eq!(terrain.trees(far), terrain.trees(wrapped));
+    }
+
+    #[test]
+    fn test_trees_by_slope() {
+        let terrain = example();
+        let slopes =
+            [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)].map(|(right, down)| Slope { right, down });
+        assert_eq!(terrain.trees_by_slope(&slopes), vec![2, 7, 3, 4, 2]);
+        assert_eq!(terrain.trees_by_slope(&[]), Vec::<usize>::new());
+
+        let mut rng = Rng(0x2020_0003);
+        for _ in 0..50 {
+            let (width, height) = (rng.below(20) + 1, rng.below(40));
+            let rows =
+                (0..height).map(|_| (0..width).map(|_| rng.below(3) == 0).collect::<Vec<_>>());
+            let terrain = Terrain::new(rows.collect::<Vec<_>>()).unwrap();
+            let slopes: Vec<_> = (0..10)
+                .map(|_| Slope {
+                    right: rng.below(50) as usize,
+                    down: rng.below(5) as usize + 1,
+                })
+                .collect();
+            let expected: Vec<_> = slopes
+                .iter()
+                .map(|&slope| terrain.path(slope).filter(|v| v.tree).count())
+                .collect();
+            assert_eq!(terrain.trees_by_slope(&slopes), expected, "{:?}", slopes);
+        }
+    }
+
+    #[test]
+    fn test_find_slope() {
+        let terrain = example();
+        assert_eq!(
+            terrain.find_slope(1..=7, 1..=2, Goal::MostTrees),
+            Some((Slope { right: 3, down: 1 }, 7))
+        );
+        assert_eq!(
+            terrain.find_slope(0..=7, 0..=2, Goal::FewestTrees),
+            Some((Slope { right: 5, down: 2 }, 0))
+        );
+        assert_eq!(terrain.find_slope(1..=7, 0..=0, Goal::MostTrees), None);
+    }
+
+    #[test]
+    fn test_render() {
+        let terrain = example();
+        let path: Vec<_> = terrain.path(Slope { right: 3, down: 1 }).collect();
+        let rendered = terrain.render(&path);
+        let rows: Vec<_> = rendered.lines().collect();
+        assert_eq!(rows.len(), 11);
+        assert_eq!(rows[0], "O.##.........##.........##.......");
+        assert_eq!(rows[1], "#..O#...#..#...#...#..#...#...#..");
+        assert_eq!(rows[2], ".#....X..#..#....#..#..#....#..#.");
+        assert_eq!(rows[10], ".#..#...#.#.#..#...#.#.#..#...X.#");
+        assert_eq!(Terrain::new([[false, true]]).unwrap().render(&[]), ".#\n");
+    }
+}
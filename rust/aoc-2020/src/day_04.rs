@@ -1,26 +1,13 @@
+use aoc_core::{records, Input, ParseError, ParseErrorKind, Solution};
 use anyhow::Result;
-use itertools::Itertools;
 use lazy_static::lazy_static;
 use regex::Regex;
 use serde::Deserialize;
-use std::collections::HashMap;
-use std::fs;
 use std::str::FromStr;
-use thiserror::Error;
-
-pub const FILEPATH: &str = "data/day_04/input.txt";
-
-#[derive(Debug, Error)]
-enum PassportParseError {
-    #[error("Ivalid input string: {0}")]
-    InvalidInputString(String),
-
-    #[error("Invalid fields: {0}")]
-    InvalidFields(String),
-}
 
+/// A passport with every required field. Other fields are ignored.
 #[derive(Clone, Debug, Deserialize, PartialEq)]
-struct Passport {
+pub struct Passport {
     byr: String,
     iyr: String,
     eyr: String,
@@ -32,18 +19,10 @@ struct Passport {
 }
 
 impl FromStr for Passport {
-    type Err = PassportParseError;
+    type Err = ParseError;
 
-    fn from_str(s: &str) -> Result<Self, PassportParseError> {
-        let map: HashMap<&str, &str> = s
-            .split(&[' ', ':'][..])
-            .tuple_windows::<(&str, &str)>()
-            .collect();
-        let json = serde_json::to_string(&map)
-            .map_err(|e| PassportParseError::InvalidInputString(e.to_string()))?;
-        let passport: Passport = serde_json::from_str(&json)
-            .map_err(|e| PassportParseError::InvalidFields(e.to_string()))?;
-        Ok(passport)
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        records::from_str(s)
     }
 }
 
@@ -65,9 +44,9 @@ fn valid_hgt(value: &str) -> bool {
     }
     if let Some(caps) = HGT.captures(value) {
         if let Some(cms) = caps.get(1) {
-            return (150..=193).contains(&str::parse::<u32>(cms.as_str()).unwrap());
+            return matches!(str::parse::<u32>(cms.as_str()), Ok(n) if (150..=193).contains(&n));
         } else if let Some(inches) = caps.get(2) {
-            return (59..=76).contains(&str::parse::<u32>(inches.as_str()).unwrap());
+            return matches!(str::parse::<u32>(inches.as_str()), Ok(n) if (59..=76).contains(&n));
         } else {
             return false;
         }
@@ -83,9 +62,7 @@ fn valid_hcl(value: &str) -> bool {
 }
 
 fn valid_ecl(value: &str) -> bool {
-    ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"]
-        .iter()
-        .any(|&x| x == value)
+    ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"].contains(&value)
 }
 
 fn valid_pid(value: &str) -> bool {
@@ -107,44 +84,52 @@ impl Passport {
     }
 }
 
-pub fn part1() -> Result<usize> {
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r"[\n\t]").unwrap();
-    }
+/// Parses the passports that have every required field, skipping the others. Any other error,
+/// such as a pair without a `:`, is returned.
+pub fn parse(input: &Input) -> Result<Vec<Passport>> {
+    Ok(records::from_batch(input)
+        .filter(|passport| !matches!(passport, Err(e) if e.kind == ParseErrorKind::MissingField))
+        .collect::<Result<_, _>>()?)
+}
 
-    let content = fs::read_to_string(FILEPATH)?;
-    let valid_passports = content
-        .split("\n\n")
-        .map(|s| str::parse(&RE.replace_all(s, " ")))
-        .filter(Result::is_ok)
-        .collect::<Result<Vec<Passport>, _>>()?;
-    Ok(valid_passports.len())
+pub fn part1(passports: &[Passport]) -> Result<usize> {
+    Ok(passports.len())
 }
 
-pub fn part2() -> Result<usize> {
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r"[\n\t]").unwrap();
+pub fn part2(passports: &[Passport]) -> Result<usize> {
+    Ok(passports.iter().filter(|p| p.is_valid()).count())
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 4;
+
+    type Model = Vec<Passport>;
+    type Answer = usize;
+
+    fn parse(input: &Input) -> Result<Vec<Passport>> {
+        parse(input)
     }
 
-    let content = fs::read_to_string(FILEPATH)?;
-    Ok(content
-        .split("\n\n")
-        .map(|s| str::parse(&RE.replace_all(s, " ")))
-        .filter(Result::is_ok)
-        .collect::<Result<Vec<Passport>, _>>()?
-        .into_iter()
-        .filter(Passport::is_valid)
-        .count())
+    fn part1(model: &Vec<Passport>) -> Result<usize> {
+        part1(model)
+    }
+
+    fn part2(model: &Vec<Passport>) -> Result<usize> {
+        part2(model)
+    }
 }
 
 #[cfg(test)]
 mod test {
+    use aoc_core::answers::expected;
+    use aoc_core::testing::puzzle_input;
+    use aoc_core::Part;
 
     use super::*;
 
-    const PART1_RESULT: usize = 202;
-    const PART2_RESULT: usize = 137;
-
     #[test]
     fn test_passport_deserialize() {
         let s = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd byr:1937 iyr:2017 cid:147 hgt:183cm";
@@ -164,16 +149,37 @@ mod test {
         );
 
         let s = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd byr:1937 iyr:2017 cid:147";
-        match str::parse::<Passport>(s) {
-            Err(PassportParseError::InvalidFields(_)) => (),
-            _ => panic!("Expected a PassportParseError::InvalidFields error"),
-        }
+        let err = str::parse::<Passport>(s).unwrap_err();
+        assert_eq!(err.message, "missing field `hgt`");
+        assert_eq!(err.kind, ParseErrorKind::MissingField);
+
+        let s = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd byr:1937 iyr:2017 hgt:183cm x:y:z";
+        assert_eq!(str::parse::<Passport>(s).unwrap().hgt, "183cm");
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = Input::new(
+            "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd\nbyr:1937 iyr:2017 cid:147 hgt:183cm\n\n\
+             iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884",
+        );
+        assert_eq!(parse(&input).unwrap().len(), 1);
+
+        let input = Input::new("ecl:gry pid:860033327 eyr:2020\n\nhcl:#fffffd byr:1937 iyr");
+        let err = parse(&input).unwrap_err().downcast::<ParseError>().unwrap();
+        assert_eq!(
+            (err.line, err.column, err.message.as_str()),
+            (3, 22, "expected key:value")
+        );
     }
 
     #[test]
     fn check_part1() {
-        let result = part1().unwrap();
-        assert_eq!(result, PART1_RESULT);
+        let Some(input) = puzzle_input(2020, 4) else {
+            return;
+        };
+        let result = part1(&parse(&input).unwrap()).unwrap();
+        assert_eq!(Some(result.into()), expected(2020, 4, Part::One));
     }
 
 	#[test]
@@ -272,7 +278,10 @@ mod test {
 
     #[test]
     fn check_part2() {
-        let result = part2().unwrap();
-        assert_eq!(result, PART2_RESULT);
+        let Some(input) = puzzle_input(2020, 4) else {
+            return;
+        };
+        let result = part2(&parse(&input).unwrap()).unwrap();
+        assert_eq!(Some(result.into()), expected(2020, 4, Part::Two));
     }
 }
@@ -0,0 +1,169 @@
+//! Finding `k` values of a list that sum to a target: the k-sum problem.
+//!
+//! Pairs are found by hashing in O(n). Larger combinations sort the values, fix all but two of
+//! them, and find the last two with a pair of pointers closing in from both ends, in O(n^(k-1)).
+
+use std::collections::{BTreeSet, HashSet};
+
+/// Finds `k` values, taken from distinct positions of `values`, that sum to `target`.
+pub fn find_k_sum(values: &[i64], k: usize, target: i64) -> Option<Vec<i64>> {
+    k_sums(values, k, target, false).pop()
+}
+
+/// Every combination of `k` values, taken from distinct positions of `values`, that sums to
+/// `target`. Each combination is sorted and listed once, in ascending order.
+pub fn find_all_k_sums(values: &[i64], k: usize, target: i64) -> Vec<Vec<i64>> {
+    k_sums(values, k, target, true)
+}
+
+fn k_sums(values: &[i64], k: usize, target: i64, all: bool) -> Vec<Vec<i64>> {
+    if k == 2 {
+        return pair_sums(values, target, all);
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+    let mut found = vec![];
+    sorted_sums(&sorted, k, target as i128, &mut vec![], &mut found, all);
+    found
+}
+
+/// Pairs summing to `target`, by looking up the complement of each value among those before it.
+fn pair_sums(values: &[i64], target: i64, all: bool) -> Vec<Vec<i64>> {
+    let mut seen = HashSet::new();
+    let mut found = BTreeSet::new();
+    for &y in values {
+        let complement = i64::try_from(target as i128 - y as i128).ok();
+        if let Some(x) = complement.filter(|x| seen.contains(x)) {
+            found.insert((x.min(y), x.max(y)));
+            if !all {
+                break;
+            }
+        }
+        seen.insert(y);
+    }
+    found.into_iter().map(|(x, y)| vec![x, y]).collect()
+}
+
+/// Extends `prefix` with `k` values of the sorted slice `values` summing to `target`. Equal
+/// values are only tried once at each position, so no combination is found twice.
+fn sorted_sums(
+    values: &[i64],
+    k: usize,
+    target: i128,
+    prefix: &mut Vec<i64>,
+    found: &mut Vec<Vec<i64>>,
+    all: bool,
+) {
+    if (!all && !found.is_empty()) || values.len() < k {
+        return;
+    }
+    match k {
+        0 => {
+            if target == 0 {
+                found.push(prefix.clone());
+            }
+        }
+        1 => {
+            if values
+                .binary_search_by(|&v| (v as i128).cmp(&target))
+                .is_ok()
+            {
+                found.push([prefix.as_slice(), &[target as i64]].concat());
+            }
+        }
+        2 => {
+            let (mut i, mut j) = (0, values.len() - 1);
+            while i < j {
+                let (x, y) = (values[i], values[j]);
+                match (x as i128 + y as i128).cmp(&target) {
+                    std::cmp::Ordering::Less => i += 1,
+                    std::cmp::Ordering::Greater => j -= 1,
+                    std::cmp::Ordering::Equal => {
+                        found.push([prefix.as_slice(), &[x, y]].concat());
+                        if !all {
+                            return;
+                        }
+                        while i < j && values[i] == x {
+                            i += 1;
+                        }
+                        while i < j && values[j] == y {
+                            j -= 1;
+                        }
+                    }
+                }
+            }
+        }
+        _ => {
+            for i in 0..=values.len() - k {
+                if i > 0 && values[i] == values[i - 1] {
+                    continue;
+                }
+                prefix.push(values[i]);
+                let rest = target - values[i] as i128;
+                sorted_sums(&values[i + 1..], k - 1, rest, prefix, found, all);
+                prefix.pop();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_core::testing::Rng;
+    use itertools::Itertools;
+
+    use super::*;
+
+    fn brute_force(values: &[i64], k: usize, target: i64) -> Vec<Vec<i64>> {
+        values
+            .iter()
+            .copied()
+            .combinations(k)
+            .filter(|c| c.iter().sum::<i64>() == target)
+            .map(|c| c.into_iter().sorted().collect())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn test_examples() {
+        let values = [1721, 979, 366, 299, 675, 1456];
+        assert_eq!(find_k_sum(&values, 2, 2020), Some(vec![299, 1721]));
+        assert_eq!(find_k_sum(&values, 3, 2020), Some(vec![366, 675, 979]));
+        assert_eq!(find_k_sum(&values, 2, 1), None);
+        assert_eq!(find_k_sum(&[1010], 2, 2020), None);
+        assert_eq!(find_all_k_sums(&[1, 1, 2, 2], 2, 3), vec![vec![1, 2]]);
+        assert_eq!(find_all_k_sums(&[5, -5], 0, 0), vec![Vec::<i64>::new()]);
+        assert_eq!(find_k_sum(&[i64::MAX, i64::MIN, 1], 2, 0), None);
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        let mut rng = Rng(0x2020_0001);
+        for _ in 0..500 {
+            let n = rng.below(12) as usize;
+            let k = rng.below(6) as usize;
+            let values: Vec<i64> = (0..n).map(|_| rng.below(21) as i64 - 10).collect();
+            let target = rng.below(41) as i64 - 20;
+            let expected = brute_force(&values, k, target);
+            assert_eq!(
+                find_all_k_sums(&values, k, target),
+                expected,
+                "{:?}",
+                (&values, k, target)
+            );
+            match find_k_sum(&values, k, target) {
+                Some(mut found) => {
+                    found.sort_unstable();
+                    assert!(
+                        expected.contains(&found),
+                        "{:?}",
+                        (&values, k, target, found)
+                    );
+                }
+                None => assert!(expected.is_empty(), "{:?}", (&values, k, target)),
+            }
+        }
+    }
+}
@@ -1,26 +1,22 @@
+use aoc_core::{Input, Solution};
 use anyhow::Result;
 use itertools::Itertools;
-use std::fs;
 
-const FILEPATH: &str = "data/day_01/input.txt";
+pub fn parse(input: &Input) -> Result<Vec<u64>> {
+    Ok(input.ints()?)
+}
 
-pub fn part1() -> Result<usize> {
-    Ok(fs::read_to_string(FILEPATH)?
-        .split('\n')
-        .map(str::parse::<u64>)
-        .collect::<Result<Vec<_>, _>>()?
-        .into_iter()
+pub fn part1(depths: &[u64]) -> Result<usize> {
+    Ok(depths
+        .iter()
         .tuple_windows()
         .filter(|(x, y)| x < y)
         .count())
 }
 
-pub fn part2() -> Result<usize> {
-    Ok(fs::read_to_string(FILEPATH)?
-        .split('\n')
-        .map(str::parse::<u64>)
-        .collect::<Result<Vec<_>, _>>()?
-        .into_iter()
+pub fn part2(depths: &[u64]) -> Result<usize> {
+    Ok(depths
+        .iter()
         .tuple_windows()
         .map(|(x, y, z)| x + y + z)
         .collect::<Vec<_>>()
@@ -30,22 +26,51 @@ pub fn part2() -> Result<usize> {
         .count())
 }
 
+pub struct Day01;
+
+impl Solution for Day01 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 1;
+
+    type Model = Vec<u64>;
+    type Answer = usize;
+
+    fn parse(input: &Input) -> Result<Vec<u64>> {
+        parse(input)
+    }
+
+    fn part1(model: &Vec<u64>) -> Result<usize> {
+        part1(model)
+    }
+
+    fn part2(model: &Vec<u64>) -> Result<usize> {
+        part2(model)
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::*;
+    use aoc_core::answers::expected;
+    use aoc_core::testing::puzzle_input;
+    use aoc_core::Part;
 
-    const PART1_RESULT: usize = 1393;
-    const PART2_RESULT: usize = 1359;
+    use super::*;
 
     #[test]
     fn chck_part1() {
-        let result = part1().unwrap();
-        assert_eq!(result, PART1_RESULT);
+        let Some(input) = puzzle_input(2021, 1) else {
+            return;
+        };
+        let result = part1(&parse(&input).unwrap()).unwrap();
+        assert_eq!(Some(result.into()), expected(2021, 1, Part::One));
     }
 
     #[test]
     fn check_part2() {
-        let result = part2().unwrap();
-        assert_eq!(result, PART2_RESULT);
+        let Some(input) = puzzle_input(2021, 1) else {
+            return;
+        };
+        let result = part2(&parse(&input).unwrap()).unwrap();
+        assert_eq!(Some(result.into()), expected(2021, 1, Part::Two));
     }
 }
@@ -1,52 +1,46 @@
+use aoc_core::parse::parse_lines;
+use aoc_core::{Input, ParseError, Solution};
 use anyhow::Result;
-use itertools::Itertools;
-use std::fs;
 use std::str::FromStr;
-use thiserror::Error;
 
-const FILEPATH: &str = "data/day_02/input.txt";
-
-#[derive(Clone, Debug, Error, PartialEq)]
-pub enum CommandError {
-    #[error("Cannot parse command {0}")]
-    ParseCommandError(String),
-}
-
-#[derive(Clone, Debug, PartialEq)]
-enum Command {
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Command {
     Forward(u64),
     Up(u64),
     Down(u64),
 }
 
 impl FromStr for Command {
-    type Err = CommandError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        s.split_whitespace()
-            .tuple_windows()
-            .next()
-            .map(|(x, y)| {
-                let n = str::parse::<u64>(y)
-                    .map_err(|_| CommandError::ParseCommandError(s.to_string()))?;
-                match x {
-                    "forward" => Ok(Command::Forward(n)),
-                    "up" => Ok(Command::Up(n)),
-                    "down" => Ok(Command::Down(n)),
-                    _ => Err(CommandError::ParseCommandError(s.to_string())),
-                }
-            })
-            .unwrap_or_else(|| Err(CommandError::ParseCommandError(s.to_string())))
+        let mut words = s.split_whitespace();
+        let (Some(x), Some(y)) = (words.next(), words.next()) else {
+            return Err(ParseError::at(s, s.len(), "expected a command and a distance"));
+        };
+        let n = str::parse::<u64>(y)
+            .map_err(|e| ParseError::at_str(s, y, format!("invalid distance: {}", e)))?;
+        match x {
+            "forward" => Ok(Command::Forward(n)),
+            "up" => Ok(Command::Up(n)),
+            "down" => Ok(Command::Down(n)),
+            _ => Err(ParseError::at_str(
+                s,
+                x,
+                "unknown command, expected forward, up or down",
+            )),
+        }
     }
 }
 
-pub fn part1() -> Result<u64> {
-    let (h, d) = fs::read_to_string(FILEPATH)?
-        .split('\n')
-        .map(str::parse::<Command>)
-        .collect::<Result<Vec<_>, _>>()?
-        .into_iter()
-        .fold((0u64, 0u64), |(h, d), cmd| match cmd {
+pub fn parse(input: &Input) -> Result<Vec<Command>> {
+    Ok(parse_lines(input, str::parse)?)
+}
+
+pub fn part1(commands: &[Command]) -> Result<u64> {
+    let (h, d) = commands
+        .iter()
+        .fold((0u64, 0u64), |(h, d), &cmd| match cmd {
             Command::Forward(x) => (h + x, d),
             Command::Up(x) => (h, u64::checked_sub(d, x).unwrap()),
             Command::Down(x) => (h, d + x),
@@ -54,13 +48,10 @@ pub fn part1() -> Result<u64> {
     Ok(h * d)
 }
 
-pub fn part2() -> Result<u64> {
-    let (h, d, _aim) = fs::read_to_string(FILEPATH)?
-        .split('\n')
-        .map(str::parse::<Command>)
-        .collect::<Result<Vec<_>, _>>()?
-        .into_iter()
-        .fold((0u64, 0u64, 0i64), |(h, d, aim), cmd| match cmd {
+pub fn part2(commands: &[Command]) -> Result<u64> {
+    let (h, d, _aim) = commands
+        .iter()
+        .fold((0u64, 0u64, 0i64), |(h, d, aim), &cmd| match cmd {
             Command::Forward(x) => (h + x, (d as i64 + aim * x as i64).try_into().unwrap(), aim),
             Command::Up(x) => (h, d, aim - x as i64),
             Command::Down(x) => (h, d, aim + x as i64),
@@ -68,14 +59,36 @@ pub fn part2() -> Result<u64> {
     Ok(h * d)
 }
 
+pub struct Day02;
+
+impl Solution for Day02 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 2;
+
+    type Model = Vec<Command>;
+    type Answer = u64;
+
+    fn parse(input: &Input) -> Result<Vec<Command>> {
+        parse(input)
+    }
+
+    fn part1(model: &Vec<Command>) -> Result<u64> {
+        part1(model)
+    }
+
+    fn part2(model: &Vec<Command>) -> Result<u64> {
+        part2(model)
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use aoc_core::answers::expected;
+    use aoc_core::testing::puzzle_input;
+    use aoc_core::Part;
 
     use super::*;
 
-    const PART1_RESULT: u64 = 1938402;
-    const PART2_RESULT: u64 = 1947878632;
-
     #[test]
     fn test_command_parsing() {
         assert_eq!(str::parse::<Command>("forward 5"), Ok(Command::Forward(5)));
@@ -83,25 +96,43 @@ mod test {
             str::parse::<Command>("forward 5 abc"),
             Ok(Command::Forward(5))
         );
+        let err = str::parse::<Command>("forward").unwrap_err();
         assert_eq!(
-            str::parse::<Command>("forward"),
-            Err(CommandError::ParseCommandError("forward".to_string()))
+            (err.column, err.message.as_str()),
+            (8, "expected a command and a distance")
         );
+        let err = str::parse::<Command>("for 5").unwrap_err();
         assert_eq!(
-            str::parse::<Command>("for 5"),
-            Err(CommandError::ParseCommandError("for 5".to_string()))
+            (err.column, err.message.as_str()),
+            (1, "unknown command, expected forward, up or down")
         );
+        let err = str::parse::<Command>("up x").unwrap_err();
+        assert_eq!(err.column, 4);
+    }
+
+    #[test]
+    fn test_parse_error_line() {
+        let err = parse(&Input::new("forward 5\ndown 5\nbackward 2")).unwrap_err();
+        let err = err.downcast::<ParseError>().unwrap();
+        assert_eq!((err.line, err.column), (3, 1));
+        assert_eq!(err.text, "backward 2");
     }
 
     #[test]
     fn check_part1() {
-        let result = part1().unwrap();
-        assert_eq!(result, PART1_RESULT);
+        let Some(input) = puzzle_input(2021, 2) else {
+            return;
+        };
+        let result = part1(&parse(&input).unwrap()).unwrap();
+        assert_eq!(Some(result.into()), expected(2021, 2, Part::One));
     }
 
     #[test]
     fn check_part2() {
-        let result = part2().unwrap();
-        assert_eq!(result, PART2_RESULT);
+        let Some(input) = puzzle_input(2021, 2) else {
+            return;
+        };
+        let result = part2(&parse(&input).unwrap()).unwrap();
+        assert_eq!(Some(result.into()), expected(2021, 2, Part::Two));
     }
 }
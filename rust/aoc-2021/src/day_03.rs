@@ -1,7 +1,28 @@
+use aoc_core::parse::parse_lines;
+use aoc_core::{Input, ParseError, Solution};
 use anyhow::Result;
-use std::fs;
 
-const FILEPATH: &str = "data/day_03/input.txt";
+/// Parses one binary number per line, all of the same width.
+fn parse_bits(input: &str) -> Result<Vec<Vec<bool>>, ParseError> {
+    let mut width = None;
+    parse_lines(input, |line| {
+        if let Some(i) = line.find(|c| c != '0' && c != '1') {
+            return Err(ParseError::at(line, i, "expected a binary digit"));
+        }
+        if line.is_empty() {
+            return Err(ParseError::at(line, 0, "expected a binary number"));
+        }
+        let width = *width.get_or_insert(line.len());
+        if line.len() != width {
+            return Err(ParseError::at(
+                line,
+                line.len().min(width),
+                format!("expected {} binary digits", width),
+            ));
+        }
+        Ok(line.chars().map(|c| c == '1').collect())
+    })
+}
 
 fn bits_to_decimal(bits: &[bool]) -> u64 {
     bits.iter().rev().enumerate().fold(0u64, |sum, (i, &bit)| {
@@ -13,18 +34,15 @@ fn negate_bits(bits: &[bool]) -> Vec<bool> {
     bits.iter().map(|&x| !x).collect()
 }
 
-pub fn part1() -> Result<u64> {
-    let content = fs::read_to_string(FILEPATH)?;
-    let lines = content.split('\n').collect::<Vec<&str>>();
-    let size = lines[0].len();
-    let length = lines.len();
-    let gamma_bits: Vec<bool> = lines
+pub fn parse(input: &Input) -> Result<Vec<Vec<bool>>> {
+    Ok(parse_bits(input)?)
+}
+
+pub fn part1(numbers: &[Vec<bool>]) -> Result<u64> {
+    let size = numbers[0].len();
+    let length = numbers.len();
+    let gamma_bits: Vec<bool> = numbers
         .iter()
-        .map(|&s| {
-            let mut bits = vec![false; size];
-            s.chars().enumerate().for_each(|(i, c)| bits[i] = c == '1');
-            bits
-        })
         .fold(vec![0usize; size], |ones, bits| {
             ones.iter()
                 .zip(bits.iter())
@@ -67,20 +85,7 @@ fn filter_numbers<'a>(numbers: &[&'a Vec<bool>], pos: usize, flip: bool) -> Vec<
     }
 }
 
-pub fn part2() -> Result<u64> {
-    let content = fs::read_to_string(FILEPATH)?;
-    let lines = content.split('\n').collect::<Vec<&str>>();
-    let size = lines[0].len();
-
-    let bit_numbers: Vec<Vec<bool>> = lines
-        .iter()
-        .map(|&s| {
-            let mut bits = vec![false; size];
-            s.chars().enumerate().for_each(|(i, c)| bits[i] = c == '1');
-            bits
-        })
-        .collect();
-
+pub fn part2(bit_numbers: &[Vec<bool>]) -> Result<u64> {
     let ogr = bits_to_decimal(
         filter_numbers(&bit_numbers.iter().collect::<Vec<&Vec<bool>>>(), 0, false)[0],
     );
@@ -90,20 +95,55 @@ pub fn part2() -> Result<u64> {
     Ok(ogr * csr)
 }
 
+pub struct Day03;
+
+impl Solution for Day03 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 3;
+
+    type Model = Vec<Vec<bool>>;
+    type Answer = u64;
+
+    fn parse(input: &Input) -> Result<Vec<Vec<bool>>> {
+        parse(input)
+    }
+
+    fn part1(model: &Vec<Vec<bool>>) -> Result<u64> {
+        part1(model)
+    }
+
+    fn part2(model: &Vec<Vec<bool>>) -> Result<u64> {
+        part2(model)
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use aoc_core::answers::expected;
+    use aoc_core::testing::puzzle_input;
+    use aoc_core::Part;
 
     use super::*;
 
-    const PART1_RESULT: u64 = 3687446;
-    const PART2_RESULT: u64 = 4406844;
-
     #[test]
     fn test_bits_to_decimal() {
         let bits = [true, true, false, false, true];
         assert_eq!(bits_to_decimal(&bits), 25);
     }
 
+    #[test]
+    fn test_parse_bits() {
+        assert_eq!(
+            parse_bits("10\n01").unwrap(),
+            vec![vec![true, false], vec![false, true]]
+        );
+        let err = parse_bits("101\n1001").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 4));
+        assert_eq!(err.message, "expected 3 binary digits");
+        let err = parse_bits("101\n121").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+    }
+
     #[test]
     fn test_negate_bits() {
         let bits = [true, true, false, false, true];
@@ -112,8 +152,11 @@ mod test {
 
     #[test]
     fn check_part1() {
-        let result = part1().unwrap();
-        assert_eq!(result, PART1_RESULT);
+        let Some(input) = puzzle_input(2021, 3) else {
+            return;
+        };
+        let result = part1(&parse(&input).unwrap()).unwrap();
+        assert_eq!(Some(result.into()), expected(2021, 3, Part::One));
     }
 
     #[test]
@@ -135,7 +178,10 @@ mod test {
 
     #[test]
     fn check_part2() {
-        let result = part2().unwrap();
-        assert_eq!(result, PART2_RESULT);
+        let Some(input) = puzzle_input(2021, 3) else {
+            return;
+        };
+        let result = part2(&parse(&input).unwrap()).unwrap();
+        assert_eq!(Some(result.into()), expected(2021, 3, Part::Two));
     }
 }
@@ -1,10 +1,8 @@
-use std::fs;
+use aoc_core::{Input, Solution};
 use anyhow::Result;
 use phf::phf_map;
 
 
-const FILEPATH: &str = "data/day_01/input.txt";
-
 const DIGIT_MAP: phf::Map<&'static str, char> = phf_map! {
     "one" => '1',
     "two" => '2',
@@ -17,23 +15,19 @@ const DIGIT_MAP: phf::Map<&'static str, char> = phf_map! {
     "nine" => '9'
 };
 
-pub fn run() -> Result<()> {
-   let input = fs::read_to_string(FILEPATH)?;
-   println!("Part1: {}", part1(&input)?);
-   println!("Part2: {}", part2(&input)?);
-
-   Ok(())
+pub fn parse(input: &Input) -> Result<Vec<String>> {
+    Ok(input.lines().map(str::to_string).collect())
 }
 
-pub fn part1(input: &str) -> Result<u32> {
+pub fn part1(lines: &[String]) -> Result<u32> {
 
-    let result = input 
-        .split('\n')
+    let result = lines
+        .iter()
         .map(|line| {
             let mut digits = line.chars()
                 .filter(|c| c.is_ascii_digit());
             let first = digits.next().unwrap_or('0');
-            let last = digits.last().unwrap_or(first);
+            let last = digits.next_back().unwrap_or(first);
             10u32 * first.to_digit(10).unwrap() + last.to_digit(10).unwrap()
         })
         .sum::<u32>();
@@ -41,10 +35,10 @@ pub fn part1(input: &str) -> Result<u32> {
     Ok(result)
 }
 
-pub fn part2(input: &str) -> Result<u32> {
+pub fn part2(lines: &[String]) -> Result<u32> {
 
-    let result = input.split('\n')
-        .map(to_digits)
+    let result = lines.iter()
+        .map(|line| to_digits(line))
         .map(|digits| {
             let first = digits.first().unwrap_or(&0u32);
             let last = digits.last().unwrap_or(first);
@@ -75,37 +69,48 @@ fn to_digits(input: &str) -> Vec<u32> {
 
 
 
+pub struct Day01;
+
+impl Solution for Day01 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 1;
+
+    type Model = Vec<String>;
+    type Answer = u32;
+
+    fn parse(input: &Input) -> Result<Vec<String>> {
+        parse(input)
+    }
+
+    fn part1(model: &Vec<String>) -> Result<u32> {
+        part1(model)
+    }
+
+    fn part2(model: &Vec<String>) -> Result<u32> {
+        part2(model)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
-    const TEST_DATA_PART1: &str = "\
-        1abc2
-        pqr3stu8vwx
-        a1b2c3d4e5f
-        treb7uchet";
+    const TEST_DATA_PART1: &str = include_str!("../data/day_01/example_1.txt");
 
     #[test]
     fn test_part1() {
         assert_eq!(
-            part1(TEST_DATA_PART1).unwrap(),
+            part1(&parse(&Input::new(TEST_DATA_PART1)).unwrap()).unwrap(),
             142,
         )
     }
 
-    const TEST_DATA_PART2: &str = 
-        "two1nine\n\
-         eightwothree\n\
-         abcone2threexyz\n\
-         xtwone3four\n\
-         4nineeightseven2\n\
-         zoneight234\n\
-         7pqrstsixteen";
+    const TEST_DATA_PART2: &str = include_str!("../data/day_01/example_2.txt");
     
     #[test]
     fn test_part2() {
         assert_eq!(
-            part2(TEST_DATA_PART2).unwrap(),
+            part2(&parse(&Input::new(TEST_DATA_PART2)).unwrap()).unwrap(),
             281,
         )
     }
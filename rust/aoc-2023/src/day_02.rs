@@ -1,5 +1,5 @@
-use std::fs;
-
+use aoc_core::parse::parse_lines;
+use aoc_core::{Input, Param, ParseError, Solution};
 use anyhow::Result;
 
 pub struct Setup {
@@ -8,25 +8,27 @@ pub struct Setup {
     pub blue: u32,
 }
 
-const SETUP: Setup = Setup {
-    red: 12,
-    green: 13,
-    blue: 14,
-};
-
-const FILEPATH: &str = "data/day_02/input.txt";
+const RED: Param = Param::new("red", "12", "Red cubes in the bag");
+const GREEN: Param = Param::new("green", "13", "Green cubes in the bag");
+const BLUE: Param = Param::new("blue", "14", "Blue cubes in the bag");
 
+impl Setup {
+    fn from_params(input: &Input) -> Result<Setup> {
+        Ok(Setup {
+            red: input.param(&RED)?,
+            green: input.param(&GREEN)?,
+            blue: input.param(&BLUE)?,
+        })
+    }
+}
 
-pub fn run() -> Result<()> {
-    let input = fs::read_to_string(FILEPATH)?;
-    println!("Part1: {}", part1(&SETUP, &input)?);
-    println!("Part2: {}", part2(&input)?);
-    Ok(())
+pub fn parse(input: &Input) -> Result<Vec<Game>, ParseError> {
+    parse_lines(input, parse_game)
 }
 
-pub fn part1(setup: &Setup, input: &str) -> Result<u32> {
-    let sum = input.split('\n')
-        .map(|line| parse_game(line).unwrap())
+pub fn part1(setup: &Setup, games: &[Game]) -> Result<u32> {
+    let sum = games
+        .iter()
         .filter(|g| {
             let max = g.max();
             max.0 <= setup.red && max.1 <= setup.green && max.2 <= setup.blue
@@ -36,9 +38,9 @@ pub fn part1(setup: &Setup, input: &str) -> Result<u32> {
     Ok(sum)
 }
 
-pub fn part2(input: &str) -> Result<u32> {
-    let sum = input.split('\n')
-        .map(|line| parse_game(line).unwrap()) 
+pub fn part2(games: &[Game]) -> Result<u32> {
+    let sum = games
+        .iter()
         .map(|g| g.power())
         .sum();
     Ok(sum)
@@ -68,53 +70,36 @@ impl Game {
     }
 }
 
-fn parse_game(input: &str) -> Result<Game> {
-    let (_, game) = parser::game(input).unwrap();
-    Ok(game.clone())
-} 
+fn parse_game(input: &str) -> Result<Game, ParseError> {
+    ParseError::finish(input, parser::game(input))
+}
 
 mod parser {
+    use aoc_parse::{labelled, separated, ws};
     use nom::{
         IResult, 
         bytes::complete::tag, 
-        character::complete::{char, u32, multispace0}, 
-        sequence::{separated_pair, preceded, delimited, terminated}, 
+        character::complete::{char, u32}, 
+        sequence::terminated, 
         combinator::{map, opt}, 
-        error::ParseError, multi::separated_list0, branch::alt 
+        branch::alt 
     };
     use super::*;
 
-    /// A combinator that takes a parser `inner` and produces a parser that also consumes both leading and 
-    /// trailing whitespace, returning the output of `inner`.
-    fn ws<'a, F: 'a, O, E: ParseError<&'a str>>(inner: F) -> impl FnMut(&'a str) -> IResult<&'a str, O, E>
-    where
-        F: Fn(&'a str) -> IResult<&'a str, O, E>,
-    {
-        delimited(
-            multispace0,
-            inner,
-            multispace0
-        )
-    }
-
     pub fn game(input: &str) -> IResult<&str, Game> {
         map(
-            separated_pair(game_id, char(':'), terminated(sets, opt(char('\n')))),
+            labelled("Game", terminated(sets, opt(char('\n')))),
             |(id, sets)| Game { id, sets }
         )(input)
     }
 
-    fn game_id(input: &str) -> IResult<&str, u32> {
-        preceded(tag("Game"), ws(u32))(input)
-    }
-
     fn sets(input: &str) -> IResult<&str, Vec<(u32, u32, u32)>> {
-        separated_list0(char(';'), set)(input)
+        separated(';', set)(input)
     }
    
     fn set(input: &str) -> IResult<&str, (u32, u32, u32)> {
         map(
-            separated_list0(tag(","), alt((red, green, blue))),
+            separated(',', alt((red, green, blue))),
             |values| values.iter()
                 .fold((0, 0, 0), |acc, num| {
                     (
@@ -152,12 +137,14 @@ mod parser {
     #[cfg(test)]
     mod test {
 
+        use aoc_parse::labelled_id;
+
         use super::*;
 
         #[test]
         fn test_game_id() {
             assert_eq!(
-                game_id("Game 1"),
+                labelled_id::<nom::error::Error<&str>>("Game")("Game 1"),
                 Ok(("", 1))
             );
         }
@@ -233,30 +220,62 @@ mod parser {
 }
 
 
+pub struct Day02;
+
+impl Solution for Day02 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 2;
+    const PARAMS: &'static [Param] = &[RED, GREEN, BLUE];
+
+    type Model = (Setup, Vec<Game>);
+    type Answer = u32;
+
+    fn parse(input: &Input) -> Result<(Setup, Vec<Game>)> {
+        Ok((Setup::from_params(input)?, parse(input)?))
+    }
+
+    fn part1((setup, games): &(Setup, Vec<Game>)) -> Result<u32> {
+        part1(setup, games)
+    }
+
+    fn part2((_, games): &(Setup, Vec<Game>)) -> Result<u32> {
+        part2(games)
+    }
+}
+
 #[cfg(test)]
 mod test {
 
     use super::*;
 
-    const TEST_DATA: &str = 
-        "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\n\
-         Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue\n\
-         Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red\n\
-         Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red\n\
-         Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+    const TEST_DATA: &str = include_str!("../data/day_02/example_1.txt");
+    const SETUP: Setup = Setup {
+        red: 12,
+        green: 13,
+        blue: 14,
+    };
     
     #[test]
     fn test_part1() {
         assert_eq!(
-            part1(&SETUP, TEST_DATA).unwrap(),
+            part1(&SETUP, &parse(&Input::new(TEST_DATA)).unwrap()).unwrap(),
             8,
         );
     }
 
+    #[test]
+    fn test_setup_params() {
+        let input = Input::new(TEST_DATA)
+            .with_params([("red", "20"), ("blue", "15")].into_iter().collect());
+        let (setup, games) = Day02::parse(&input).unwrap();
+        assert_eq!((setup.red, setup.green, setup.blue), (20, 13, 15));
+        assert_eq!(part1(&setup, &games).unwrap(), 15);
+    }
+
     #[test]
     fn test_part2() {
         assert_eq!(
-            part2(TEST_DATA).unwrap(),
+            part2(&parse(&Input::new(TEST_DATA)).unwrap()).unwrap(),
             2286
         );
     }
@@ -300,6 +319,13 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_parse_error() {
+        let err = parse(&Input::new("Game 1: 3 blue, 4 red\nGame 2: 1 blue; x green")).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 17));
+        assert_eq!(err.text, "Game 2: 1 blue; x green");
+    }
+
     #[test]
     fn test_max() {
         assert_eq!(
@@ -1,35 +1,16 @@
-use std::fs;
-
+use aoc_core::{Input, Solution};
 use anyhow::Result;
 
-
-const FILEPATH: &str = "data/day_03/input.txt";
-
-
-pub fn run() -> Result<()> {
-    let input = fs::read_to_string(FILEPATH)?;
-    println!("Part1: {}", part1(&input)?);
-    println!("Part2: {}", part2(&input)?);
-    Ok(())
+pub fn parse(input: &Input) -> Result<Diagram> {
+    Ok(Diagram::new(input))
 }
 
-pub fn part1(input: &str) -> Result<u32> {
-    let diagram = Diagram::new(input);
-    let result = diagram.get_valid_parts().iter()
-        .map(|p| p.id)
-        .sum::<u32>();
-    Ok(result)
+pub fn part1(diagram: &Diagram) -> Result<u32> {
+    Ok(diagram.part_number_sum())
 }
 
-pub fn part2(input: &str) -> Result<u32> {
-    let diagram = Diagram::new(input);
-    let result = diagram.get_gears()
-        .iter()
-        .map(|parts| parts.iter()
-            .fold(1, |acc, &part| acc * part.id)
-        ).sum::<u32>();
-    Ok(result)
-        
+pub fn part2(diagram: &Diagram) -> Result<u32> {
+    Ok(diagram.gear_ratio_sum())
 }
 
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -102,8 +83,8 @@ pub struct Diagram {
 }
 
 impl Diagram {
-    pub fn new(input: &str) -> Self {
-        let lines = input.split('\n')
+    pub fn new(input: &Input) -> Self {
+        let lines = input.lines()
             .map(Line::new)
             .collect::<Vec<_>>();
         
@@ -155,32 +136,58 @@ impl Diagram {
             .filter(|parts| parts.len() == 2)
             .collect::<Vec<_>>() 
     }
+
+    pub fn part_number_sum(&self) -> u32 {
+        self.get_valid_parts().iter()
+            .map(|p| p.id)
+            .sum::<u32>()
+    }
+
+    pub fn gear_ratio_sum(&self) -> u32 {
+        self.get_gears()
+            .iter()
+            .map(|parts| parts.iter()
+                .fold(1, |acc, &part| acc * part.id)
+            ).sum::<u32>()
+    }
 }
 
 fn is_adjacent(bounds: &(u32, u32), pos: &u32) -> bool {
     (bounds.0 as i32 - 1) <= *pos as i32 && bounds.1 + 1 >= *pos
 }
 
+pub struct Day03;
+
+impl Solution for Day03 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 3;
+
+    type Model = Diagram;
+    type Answer = u32;
+
+    fn parse(input: &Input) -> Result<Diagram> {
+        parse(input)
+    }
+
+    fn part1(model: &Diagram) -> Result<u32> {
+        part1(model)
+    }
+
+    fn part2(model: &Diagram) -> Result<u32> {
+        part2(model)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
-    const TEST_DATA: &str = 
-        "467..114..\n\
-         ...*......\n\
-         ..35..633.\n\
-         ......#...\n\
-         617*......\n\
-         .....+.58.\n\
-         ..592.....\n\
-         ......755.\n\
-         ...$.*....\n\
-         .664.598..";
+    const TEST_DATA: &str = include_str!("../data/day_03/example_1.txt");
     
     #[test]
     fn test_part1() {
         assert_eq!(
-            part1(TEST_DATA).unwrap(),
+            part1(&parse(&Input::new(TEST_DATA)).unwrap()).unwrap(),
             4361
         );
     }
@@ -188,7 +195,7 @@ mod test {
     #[test]
     fn test_part2() {
         assert_eq!(
-            part2(TEST_DATA).unwrap(),
+            part2(&parse(&Input::new(TEST_DATA)).unwrap()).unwrap(),
             467835
         );
     }
@@ -233,7 +240,7 @@ mod test {
     #[test]
     fn test_diagram() {
         assert_eq!(
-            Diagram::new(TEST_DATA),
+            Diagram::new(&Input::new(TEST_DATA)),
             Diagram { lines: vec![
                 Line { parts: vec![
                     Part { id: 467, bounds: (0, 2) },
@@ -279,7 +286,7 @@ mod test {
 
     #[test]
     fn test_valid_parts() {
-        let diagram = Diagram::new(TEST_DATA);
+        let diagram = Diagram::new(&Input::new(TEST_DATA));
         assert_eq!(
             diagram.get_valid_parts(),
             vec![
@@ -297,7 +304,7 @@ mod test {
 
     #[test]
     fn test_gears() {
-        let diagram = Diagram::new(TEST_DATA);
+        let diagram = Diagram::new(&Input::new(TEST_DATA));
         assert_eq!(
             diagram.get_gears()
                 .iter()
@@ -317,7 +324,7 @@ mod test {
              ..............14..312......+..926.....*.......529..*............*...*....*.............*......%...310.......*...835..................885....\n\
              ...416../467..........................423.....*...143...132..955...356...124.........588..947....*.....512......................134&.*......";
 
-        let diagram = Diagram::new(DATA);
+        let diagram = Diagram::new(&Input::new(DATA));
         assert_eq!(
             diagram.get_valid_parts()
                 .iter()
@@ -334,7 +341,7 @@ mod test {
              ..............14..312......+..926.....*.......529..*............*.\n\
              416...../467..........................423.....*...143...132..955..";
 
-        let diagram = Diagram::new(DATA);
+        let diagram = Diagram::new(&Input::new(DATA));
         assert_eq!(
             diagram.get_valid_parts()
                 .iter()
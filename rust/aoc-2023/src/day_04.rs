@@ -1,32 +1,28 @@
-use std::{fs, collections::VecDeque};
+use std::collections::VecDeque;
 
+use aoc_core::parse::parse_lines;
+use aoc_core::{Input, ParseError, Solution};
 use anyhow::Result;
 
+pub fn parse(input: &Input) -> Result<Vec<Card>, ParseError> {
+    parse_lines(input, Card::new)
+}
 
+pub fn part1(cards: &[Card]) -> Result<u32> {
+    Ok(total_score(cards))
+}
 
-const FILEPATH: &str = "data/day_04/input.txt";
-
-pub fn run() -> Result<()> {
-    let input = fs::read_to_string(FILEPATH)?;
-    println!("Part1: {}", part1(&input)?);
-    println!("Part2: {}", part2(&input)?);
-    Ok(())
+pub fn part2(cards: &[Card]) -> Result<u32> {
+    Ok(total_cards(cards))
 }
 
-pub fn part1(input: &str) -> Result<u32> {
-    let result = input.split('\n')
-        .map(Card::new)
+fn total_score(cards: &[Card]) -> u32 {
+    cards.iter()
         .map(|c| c.score())
-        .sum::<u32>();
-
-    Ok(result)
+        .sum::<u32>()
 }
 
-pub fn part2(input: &str) -> Result<u32> {
-    let cards = input.split('\n')
-        .map(Card::new)
-        .collect::<Vec<_>>();
-
+fn total_cards(cards: &[Card]) -> u32 {
     let wins = cards.iter()
         .map(|c| c.wins())
         .collect::<Vec<_>>();
@@ -44,7 +40,7 @@ pub fn part2(input: &str) -> Result<u32> {
         }
     }
 
-    Ok(total)
+    total
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -55,9 +51,8 @@ pub struct Card {
 }
 
 impl Card {
-    pub fn new(input: &str) -> Self {
-        let (_, card) = parser::card(input).unwrap();
-        card
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        ParseError::finish(input, parser::card(input))
     }
 
     pub fn matches(&self) -> usize {
@@ -85,42 +80,24 @@ impl Card {
 
 
 mod parser {
+    use aoc_parse::{labelled, uints};
     use nom::{
         IResult, 
         bytes::complete::tag, 
-        character::complete::{char, u32, multispace0}, 
-        sequence::{separated_pair, preceded, delimited}, 
-        combinator::map, 
-        error::ParseError, multi::many0 
+        sequence::separated_pair, 
+        combinator::map 
     };
     use super::*;
 
-    /// A combinator that takes a parser `inner` and produces a parser that also consumes both leading and 
-    /// trailing whitespace, returning the output of `inner`.
-    fn ws<'a, F: 'a, O, E: ParseError<&'a str>>(inner: F) -> impl FnMut(&'a str) -> IResult<&'a str, O, E>
-    where
-        F: Fn(&'a str) -> IResult<&'a str, O, E>,
-    {
-        delimited(
-            multispace0,
-            inner,
-            multispace0
-        )
-    }
-
     pub fn card(input: &str) -> IResult<&str, Card> {
         map(
-            separated_pair(card_id, char(':'), card_body),
+            labelled("Card", card_body),
             |(id, (winners, numbers))| Card { id, winners, numbers }
         )(input)
     }
 
-    fn card_id(input: &str) -> IResult<&str, u32> {
-        preceded(tag("Card"), ws(u32))(input)
-    }
-
     fn numbers(input: &str) -> IResult<&str, Vec<u32>> {
-        many0(ws(u32))(input)
+        uints(input)
     }
 
     fn card_body(input: &str) -> IResult<&str, (Vec<u32>, Vec<u32>)> {
@@ -131,12 +108,14 @@ mod parser {
     #[cfg(test)]
     mod test {
 
+        use aoc_parse::labelled_id;
+
         use super::*;
 
         #[test]
         fn test_card_id() {
             assert_eq!(
-                card_id("Card 1"),
+                labelled_id::<nom::error::Error<&str>>("Card")("Card 1"),
                 Ok(("", 1))
             );
         }
@@ -172,23 +151,39 @@ mod parser {
 }
 
 
+pub struct Day04;
+
+impl Solution for Day04 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 4;
+
+    type Model = Vec<Card>;
+    type Answer = u32;
+
+    fn parse(input: &Input) -> Result<Vec<Card>> {
+        Ok(parse(input)?)
+    }
+
+    fn part1(model: &Vec<Card>) -> Result<u32> {
+        part1(model)
+    }
+
+    fn part2(model: &Vec<Card>) -> Result<u32> {
+        part2(model)
+    }
+}
+
 #[cfg(test)]
 mod test {
 
     use super::*;
 
-    const TEST_DATA: &str = 
-        "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\n\
-         Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19\n\
-         Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1\n\
-         Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83\n\
-         Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36\n\
-         Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+    const TEST_DATA: &str = include_str!("../data/day_04/example_1.txt");
     
     #[test]
     fn test_part1() {
         assert_eq!(
-            part1(TEST_DATA).unwrap(),
+            part1(&parse(&Input::new(TEST_DATA)).unwrap()).unwrap(),
             13
         );
     }
@@ -196,7 +191,7 @@ mod test {
     #[test]
     fn test_part2() {
         assert_eq!(
-            part2(TEST_DATA).unwrap(),
+            part2(&parse(&Input::new(TEST_DATA)).unwrap()).unwrap(),
             30
         );
     }
@@ -204,7 +199,7 @@ mod test {
     #[test]
     fn test_parse_card() {
         assert_eq!(
-            Card::new("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53"),
+            Card::new("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53").unwrap(),
             Card {
                 id: 1,
                 winners: vec![41, 48, 83, 86, 17],
@@ -216,7 +211,7 @@ mod test {
     #[test]
     fn test_card_score() {
         assert_eq!(
-            Card::new("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53").score(),
+            Card::new("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53").unwrap().score(),
             8
         );
     }
@@ -224,8 +219,15 @@ mod test {
     #[test]
     fn test_wins() {
         assert_eq!(
-            Card::new("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53").wins(),
+            Card::new("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53").unwrap().wins(),
             vec![2, 3, 4, 5]
         );
     }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse(&Input::new("Card 1: 41 48 | 83 86\nCard 2 13 32 | 61 30")).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 8));
+        assert_eq!(err.message, "unexpected \"13\", expected a separator");
+    }
 }
@@ -0,0 +1,25 @@
+use aoc_core::{solution, DynSolution};
+
+pub mod day_01;
+pub mod day_02;
+pub mod day_03;
+pub mod day_04;
+
+pub fn solutions() -> Vec<Box<dyn DynSolution>> {
+    vec![
+        solution::<day_01::Day01>(),
+        solution::<day_02::Day02>(),
+        solution::<day_03::Day03>(),
+        solution::<day_04::Day04>(),
+    ]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        aoc_core::testing::check_examples(&solutions());
+    }
+}
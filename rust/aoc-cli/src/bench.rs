@@ -0,0 +1,59 @@
+use anyhow::{Context, Result};
+use aoc_core::bench::{self, BenchOptions};
+use clap::Args;
+
+use crate::days::Selection;
+use crate::params::ParamArgs;
+
+#[derive(Args)]
+pub struct BenchArgs {
+    #[command(flatten)]
+    selection: Selection,
+
+    /// Timed runs per stage
+    #[arg(long, default_value_t = BenchOptions::default().iterations)]
+    iterations: usize,
+
+    /// Untimed runs per stage before measuring
+    #[arg(long, default_value_t = BenchOptions::default().warmup)]
+    warmup: usize,
+
+    #[command(flatten)]
+    params: ParamArgs,
+}
+
+pub fn bench(args: &BenchArgs) -> Result<()> {
+    let selection = &args.selection;
+    let options = BenchOptions {
+        warmup: args.warmup,
+        iterations: args.iterations,
+    };
+    let source = selection.source();
+    let parts = selection.parts();
+    let solutions = selection.solutions()?;
+    let params = args.params.load(&solutions)?;
+
+    println!(
+        "{:<13} {:<7} {:>12} {:>12} {:>12}",
+        "day", "stage", "mean", "median", "p95"
+    );
+    for solution in solutions {
+        let label = format!("{} day {:02}", solution.year(), solution.day());
+        let input = source
+            .read(solution.year(), solution.day())?
+            .with_params(params.get(solution.as_ref()));
+        let results = bench::bench(solution.as_ref(), &input, &parts, &options)
+            .with_context(|| format!("Failed to benchmark {}", label))?;
+        for (stage, stats) in results {
+            println!(
+                "{:<13} {:<7} {:>12.3?} {:>12.3?} {:>12.3?}",
+                label,
+                stage.to_string(),
+                stats.mean,
+                stats.median,
+                stats.p95
+            );
+        }
+    }
+    Ok(())
+}
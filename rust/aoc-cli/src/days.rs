@@ -0,0 +1,149 @@
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use anyhow::{bail, Context, Error, Result};
+use aoc_core::{DynSolution, InputSource, Part};
+use clap::{ArgGroup, Args};
+
+use crate::years;
+
+/// A set of days given on the command line, e.g. `3`, `1..4`, `1..=4` or `1,3,5..7`.
+///
+/// Ranges follow Rust syntax: `a..b` excludes `b`, `a..=b` includes it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Days(Vec<RangeInclusive<u32>>);
+
+impl Days {
+    pub fn contains(&self, day: u32) -> bool {
+        self.0.iter().any(|r| r.contains(&day))
+    }
+}
+
+fn parse_day(s: &str) -> Result<u32> {
+    s.trim()
+        .parse()
+        .with_context(|| format!("Invalid day {:?}", s))
+}
+
+fn parse_range(s: &str) -> Result<RangeInclusive<u32>> {
+    if let Some((start, end)) = s.split_once("..=") {
+        Ok(parse_day(start)?..=parse_day(end)?)
+    } else if let Some((start, end)) = s.split_once("..") {
+        let end = parse_day(end)?;
+        if end == 0 {
+            bail!("Empty day range {:?}", s);
+        }
+        Ok(parse_day(start)?..=end - 1)
+    } else {
+        let day = parse_day(s)?;
+        Ok(day..=day)
+    }
+}
+
+impl FromStr for Days {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let ranges = s
+            .split(',')
+            .map(parse_range)
+            .collect::<Result<Vec<_>>>()?;
+        if let Some(r) = ranges.iter().find(|r| r.is_empty()) {
+            bail!("Empty day range {}..={}", r.start(), r.end());
+        }
+        Ok(Days(ranges))
+    }
+}
+
+/// Which days and parts of a year to work on, shared by the subcommands.
+#[derive(Args)]
+#[command(group(ArgGroup::new("selection").required(true).args(["day", "days", "all"])))]
+pub struct Selection {
+    /// Puzzle year
+    pub year: u32,
+
+    /// Single day
+    pub day: Option<u32>,
+
+    /// Days, e.g. `1..4`, `1..=4` or `1,3`
+    #[arg(long)]
+    pub days: Option<Days>,
+
+    /// Every day of the year
+    #[arg(long)]
+    pub all: bool,
+
+    /// Only this part
+    #[arg(long)]
+    pub part: Option<Part>,
+
+    /// Read the input from this file, or from stdin with `-`, instead of the default location
+    #[arg(long, value_name = "PATH")]
+    pub input: Option<InputSource>,
+}
+
+impl Selection {
+    fn selects(&self, day: u32) -> bool {
+        match (&self.day, &self.days) {
+            (Some(d), _) => *d == day,
+            (_, Some(days)) => days.contains(day),
+            _ => self.all,
+        }
+    }
+
+    pub fn solutions(&self) -> Result<Vec<Box<dyn DynSolution>>> {
+        let solutions = years::solutions(self.year)?
+            .into_iter()
+            .filter(|s| self.selects(s.day()))
+            .collect::<Vec<_>>();
+        if solutions.is_empty() {
+            bail!("No matching days for year {}", self.year);
+        }
+        if self.input.is_some() && solutions.len() > 1 {
+            bail!("An explicit input can only be used with a single day");
+        }
+        Ok(solutions)
+    }
+
+    pub fn parts(&self) -> Vec<Part> {
+        match self.part {
+            Some(part) => vec![part],
+            None => Part::ALL.to_vec(),
+        }
+    }
+
+    pub fn source(&self) -> InputSource {
+        self.input.clone().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_single_day() {
+        let days = "3".parse::<Days>().unwrap();
+        assert!(days.contains(3));
+        assert!(!days.contains(2));
+        assert!(!days.contains(4));
+    }
+
+    #[test]
+    fn test_ranges() {
+        let days = "1..4".parse::<Days>().unwrap();
+        assert_eq!(days, Days(vec![1..=3]));
+        let days = "1..=4".parse::<Days>().unwrap();
+        assert_eq!(days, Days(vec![1..=4]));
+        let days = "1,3,5..7".parse::<Days>().unwrap();
+        assert_eq!(days, Days(vec![1..=1, 3..=3, 5..=6]));
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!("".parse::<Days>().is_err());
+        assert!("a..3".parse::<Days>().is_err());
+        assert!("4..2".parse::<Days>().is_err());
+        assert!("1..1".parse::<Days>().is_err());
+    }
+}
@@ -0,0 +1,33 @@
+use anyhow::Result;
+use aoc_core::fetch::Fetcher;
+use clap::Args;
+use tracing::info;
+
+use crate::days::Days;
+
+#[derive(Args)]
+pub struct FetchArgs {
+    /// Puzzle year
+    year: u32,
+
+    /// Days to download, e.g. `3`, `1..4`, `1..=4` or `1,3`
+    days: Days,
+}
+
+pub fn fetch(args: &FetchArgs) -> Result<()> {
+    let fetcher = Fetcher::from_env()?;
+    for day in (1..=25).filter(|&day| args.days.contains(day)) {
+        if let Some(path) = fetcher.cached(args.year, day) {
+            info!("{} day {:02}: already cached at {}", args.year, day, path.display());
+            continue;
+        }
+        fetcher.fetch(args.year, day)?;
+        info!(
+            "{} day {:02}: downloaded to {}",
+            args.year,
+            day,
+            fetcher.cache_path(args.year, day).display()
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use std::io::{self, IsTerminal};
+
+use anyhow::Result;
+use clap::{ArgAction, Parser, Subcommand};
+use tracing::Level;
+
+mod bench;
+mod days;
+mod fetch;
+mod new;
+mod params;
+mod run;
+mod submit;
+mod verify;
+mod worker;
+mod years;
+
+#[derive(Parser)]
+#[command(name = "aoc", version, about = "Advent of Code solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    /// Log more, `-v` for intermediate values and `-vv` for everything
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
+
+    /// Log less, `-q` for warnings only and `-qq` for errors only
+    #[arg(short, long, action = ArgAction::Count, global = true, conflicts_with = "verbose")]
+    quiet: u8,
+}
+
+impl Cli {
+    /// The most detailed level to log, `info` by default.
+    fn level(&self) -> Level {
+        match 2 + self.verbose as i32 - self.quiet as i32 {
+            i32::MIN..=0 => Level::ERROR,
+            1 => Level::WARN,
+            2 => Level::INFO,
+            3 => Level::DEBUG,
+            _ => Level::TRACE,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the solutions of a year
+    Run(run::RunArgs),
+    /// Time the parse step and the parts of each day
+    Bench(bench::BenchArgs),
+    /// Check the solutions against the known answers of each year
+    Verify(verify::VerifyArgs),
+    /// Download puzzle inputs into the local cache
+    Fetch(fetch::FetchArgs),
+    /// Submit the answer of a part, unless earlier attempts rule it out
+    Submit(submit::SubmitArgs),
+    /// Add a day to a year crate, with its module and data directory
+    New(new::NewArgs),
+    /// Run parts of a day on the input from stdin, for a parent under a memory limit
+    #[command(hide = true)]
+    Worker(worker::WorkerArgs),
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    tracing_subscriber::fmt()
+        .with_max_level(cli.level())
+        .with_writer(io::stderr)
+        .with_ansi(io::stderr().is_terminal())
+        .without_time()
+        .init();
+
+    match &cli.command {
+        Command::Run(args) => run::run(args),
+        Command::Bench(args) => bench::bench(args),
+        Command::Verify(args) => verify::verify(args),
+        Command::Fetch(args) => fetch::fetch(args),
+        Command::Submit(args) => submit::submit(args),
+        Command::New(args) => new::new(args),
+        Command::Worker(args) => worker::worker(args),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn level(args: &[&str]) -> Level {
+        Cli::try_parse_from([&["aoc"], args].concat()).unwrap().level()
+    }
+
+    #[test]
+    fn test_level() {
+        assert_eq!(level(&["verify"]), Level::INFO);
+        assert_eq!(level(&["verify", "-v"]), Level::DEBUG);
+        assert_eq!(level(&["-vvv", "verify"]), Level::TRACE);
+        assert_eq!(level(&["verify", "-q"]), Level::WARN);
+        assert_eq!(level(&["verify", "-qqq"]), Level::ERROR);
+        assert!(Cli::try_parse_from(["aoc", "verify", "-v", "-q"]).is_err());
+    }
+}
@@ -0,0 +1,271 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use aoc_core::examples::EXAMPLES_FILE;
+use aoc_core::input::workspace_data_dir;
+use clap::Args;
+use tracing::info;
+
+const TEMPLATE: &str = include_str!("../templates/day.rs");
+const EXAMPLES_TEMPLATE: &str = "\
+# Paste the first example into example_1.txt and fill in its answers.
+[example_1]
+# part1 = 0
+# part2 = 0
+";
+const DEPENDENCIES: [&str; 2] = [
+    r#"aoc-parse = { path = "../aoc-parse" }"#,
+    r#"nom = "7.1.3""#,
+];
+
+#[derive(Args)]
+pub struct NewArgs {
+    /// Puzzle year, which must already have a crate
+    year: u32,
+
+    /// Puzzle day
+    day: u32,
+}
+
+fn render(year: u32, day: u32) -> String {
+    TEMPLATE
+        .replace("{{YEAR}}", &year.to_string())
+        .replace("{{DAY}}", &day.to_string())
+        .replace("{{DD}}", &format!("{:02}", day))
+}
+
+/// Inserts `line` among the consecutive lines starting with `prefix`, keeping them sorted.
+fn insert_sorted(content: &str, prefix: &str, line: &str) -> Result<String> {
+    let mut lines = content.lines().collect::<Vec<_>>();
+    let matching = (0..lines.len())
+        .filter(|&i| lines[i].starts_with(prefix))
+        .collect::<Vec<_>>();
+    let (Some(&first), Some(&last)) = (matching.first(), matching.last()) else {
+        bail!("No line starting with {:?} to insert {:?} next to", prefix, line);
+    };
+    if lines[first..=last].contains(&line) {
+        bail!("{:?} is already there", line.trim());
+    }
+    let at = (first..=last)
+        .find(|&i| lines[i] > line)
+        .unwrap_or(last + 1);
+    lines.insert(at, line);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds a dependency to a crate manifest, unless it already has it. Workspace crates stay at the
+/// top of the section and the others are kept sorted.
+fn add_dependency(manifest: &str, dependency: &str) -> Result<String> {
+    let mut lines = manifest.lines().collect::<Vec<_>>();
+    let name = dependency.split_whitespace().next().unwrap_or_default();
+    if lines
+        .iter()
+        .any(|l| l.split_whitespace().next() == Some(name))
+    {
+        return Ok(manifest.to_string());
+    }
+    let Some(section) = lines.iter().position(|&l| l == "[dependencies]") else {
+        bail!("No [dependencies] section");
+    };
+    let end = (section + 1..lines.len())
+        .find(|&i| lines[i].trim().is_empty() || lines[i].starts_with('['))
+        .unwrap_or(lines.len());
+    let is_local = |line: &str| line.contains("path =");
+    let at = (section + 1..end)
+        .find(|&i| match (is_local(dependency), is_local(lines[i])) {
+            (true, false) => true,
+            (false, true) => false,
+            _ => lines[i] > dependency,
+        })
+        .unwrap_or(end);
+    lines.insert(at, dependency);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// The content of the file at `path`, before and after `f`.
+fn updated(path: &Path, f: impl FnOnce(&str) -> Result<String>) -> Result<(String, String)> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let updated = f(&content).with_context(|| format!("Failed to update {}", path.display()))?;
+    Ok((content, updated))
+}
+
+fn create(path: &Path, content: &str) -> Result<()> {
+    fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Registers the module of `day` in `lib.rs` and adds its dependencies to `Cargo.toml`. Both are
+/// updated before either is written, and `lib.rs` is restored if `Cargo.toml` cannot be written.
+fn register(crate_dir: &Path, day: u32) -> Result<()> {
+    let lib = crate_dir.join("src").join("lib.rs");
+    let (lib_before, lib_after) = updated(&lib, |content| {
+        let content = insert_sorted(content, "pub mod day_", &format!("pub mod day_{:02};", day))?;
+        insert_sorted(
+            &content,
+            "        solution::<day_",
+            &format!("        solution::<day_{:02}::Day{:02}>(),", day, day),
+        )
+    })?;
+    let manifest = crate_dir.join("Cargo.toml");
+    let (_, manifest_after) = updated(&manifest, |manifest| {
+        DEPENDENCIES
+            .iter()
+            .try_fold(manifest.to_string(), |manifest, dependency| {
+                add_dependency(&manifest, dependency)
+            })
+    })?;
+    create(&lib, &lib_after)?;
+    if let Err(e) = create(&manifest, &manifest_after) {
+        let _ = fs::write(&lib, lib_before);
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// Adds a day to the year crate in `crate_dir`: its module, registered in `lib.rs`, and its data
+/// directory with an example to fill in. Returns the files it created. The module is removed
+/// again if it cannot be registered.
+pub fn scaffold(crate_dir: &Path, year: u32, day: u32) -> Result<Vec<PathBuf>> {
+    if !(1..=25).contains(&day) {
+        bail!("Invalid day {}, expected 1 to 25", day);
+    }
+    let module = crate_dir.join("src").join(format!("day_{:02}.rs", day));
+    if module.exists() {
+        bail!("{} already exists", module.display());
+    }
+    create(&module, &render(year, day))?;
+    if let Err(e) = register(crate_dir, day) {
+        let _ = fs::remove_file(&module);
+        return Err(e);
+    }
+
+    let data_dir = crate_dir.join("data").join(format!("day_{:02}", day));
+    fs::create_dir_all(&data_dir)
+        .with_context(|| format!("Failed to create {}", data_dir.display()))?;
+    let mut created = vec![module];
+    for (name, content) in [(EXAMPLES_FILE, EXAMPLES_TEMPLATE), ("example_1.txt", "")] {
+        let path = data_dir.join(name);
+        if !path.exists() {
+            create(&path, content)?;
+            created.push(path);
+        }
+    }
+    Ok(created)
+}
+
+pub fn new(args: &NewArgs) -> Result<()> {
+    let data_dir = workspace_data_dir(args.year)?;
+    let crate_dir = data_dir.parent().unwrap();
+    if !crate_dir.join("Cargo.toml").exists() {
+        bail!(
+            "No crate for year {}, expected one in {}",
+            args.year,
+            crate_dir.display()
+        );
+    }
+    for path in scaffold(&crate_dir.canonicalize()?, args.year, args.day)? {
+        info!("Created {}", path.display());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+
+    use super::*;
+
+    const LIB: &str = "\
+use aoc_core::{solution, DynSolution};
+
+pub mod day_01;
+pub mod day_03;
+
+pub fn solutions() -> Vec<Box<dyn DynSolution>> {
+    vec![
+        solution::<day_01::Day01>(),
+        solution::<day_03::Day03>(),
+    ]
+}
+";
+
+    const MANIFEST: &str = "\
+[package]
+name = \"aoc-2019\"
+
+[dependencies]
+aoc-core = { path = \"../aoc-core\" }
+anyhow = \"1.0\"
+regex = \"1\"
+";
+
+    fn fake_crate(test: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-{}-{}", test, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("src/lib.rs"), LIB).unwrap();
+        fs::write(dir.join("Cargo.toml"), MANIFEST).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_scaffold() {
+        let dir = fake_crate("scaffold");
+        let created = scaffold(&dir, 2019, 2).unwrap();
+        assert_eq!(
+            created,
+            vec![
+                dir.join("src/day_02.rs"),
+                dir.join("data/day_02/examples.toml"),
+                dir.join("data/day_02/example_1.txt"),
+            ]
+        );
+
+        let lib = fs::read_to_string(dir.join("src/lib.rs")).unwrap();
+        assert!(lib.contains("pub mod day_01;\npub mod day_02;\npub mod day_03;\n"));
+        assert!(lib.contains(
+            "        solution::<day_01::Day01>(),\n        \
+             solution::<day_02::Day02>(),\n        \
+             solution::<day_03::Day03>(),\n"
+        ));
+        let manifest = fs::read_to_string(dir.join("Cargo.toml")).unwrap();
+        assert!(manifest.ends_with(
+            "[dependencies]\n\
+             aoc-core = { path = \"../aoc-core\" }\n\
+             aoc-parse = { path = \"../aoc-parse\" }\n\
+             anyhow = \"1.0\"\n\
+             nom = \"7.1.3\"\n\
+             regex = \"1\"\n"
+        ));
+        let module = fs::read_to_string(dir.join("src/day_02.rs")).unwrap();
+        assert!(module.contains("impl Solution for Day02 {"));
+        assert!(module.contains("const YEAR: u32 = 2019;\n    const DAY: u32 = 2;"));
+        assert!(module.contains("include_str!(\"../data/day_02/example_1.txt\")"));
+        assert!(module.contains("#[ignore = \"part 1 is not solved yet\"]"));
+        assert!(!module.contains("{{"));
+
+        assert!(scaffold(&dir, 2019, 2).is_err());
+        assert!(scaffold(&dir, 2019, 26).is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_scaffold_rollback() {
+        let dir = fake_crate("rollback");
+        fs::write(dir.join("Cargo.toml"), "[package]\nname = \"aoc-2019\"\n").unwrap();
+        let err = scaffold(&dir, 2019, 4).unwrap_err();
+        assert!(format!("{:#}", err).contains("No [dependencies] section"));
+        assert!(!dir.join("src/day_04.rs").exists());
+        assert_eq!(fs::read_to_string(dir.join("src/lib.rs")).unwrap(), LIB);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_add_dependency_once() {
+        for dependency in DEPENDENCIES {
+            let manifest = add_dependency(MANIFEST, dependency).unwrap();
+            assert_eq!(add_dependency(&manifest, dependency).unwrap(), manifest);
+        }
+    }
+}
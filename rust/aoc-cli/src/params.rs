@@ -0,0 +1,64 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+use aoc_core::params::{self, parse_assignment};
+use aoc_core::{DynSolution, Params};
+use clap::Args;
+
+/// Puzzle parameters overriding the defaults of the days, shared by the subcommands running
+/// solutions.
+#[derive(Args)]
+pub struct ParamArgs {
+    /// Set a parameter of the selected days, e.g. `target_sum=3000`
+    #[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_assignment)]
+    params: Vec<(String, String)>,
+
+    /// Read parameters from a TOML file with a table per day, e.g. `[day_01]`
+    #[arg(long, value_name = "PATH")]
+    params_file: Option<PathBuf>,
+}
+
+/// The parameters given for each day.
+pub struct DayParams {
+    file: BTreeMap<u32, Params>,
+    given: Params,
+}
+
+impl ParamArgs {
+    /// Loads the parameters. Each one given on the command line must be taken by one of
+    /// `solutions`.
+    pub fn load(&self, solutions: &[Box<dyn DynSolution>]) -> Result<DayParams> {
+        for (name, _) in &self.params {
+            if !solutions
+                .iter()
+                .any(|s| s.params().iter().any(|p| p.name == name))
+            {
+                bail!("No selected day takes a parameter {:?}", name);
+            }
+        }
+        let file = match &self.params_file {
+            Some(path) => params::load(path)?,
+            None => BTreeMap::new(),
+        };
+        Ok(DayParams {
+            file,
+            given: self.params.iter().cloned().collect(),
+        })
+    }
+}
+
+impl DayParams {
+    /// The parameters of a day: those of its table in the file, then those of the command line
+    /// it takes.
+    pub fn get(&self, solution: &dyn DynSolution) -> Params {
+        let mut params = self.file.get(&solution.day()).cloned().unwrap_or_default();
+        let given = self
+            .given
+            .iter()
+            .filter(|(name, _)| solution.params().iter().any(|p| p.name == *name))
+            .collect();
+        params.extend(&given);
+        params
+    }
+}
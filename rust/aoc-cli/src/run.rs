@@ -0,0 +1,77 @@
+use std::io;
+
+use anyhow::{bail, Result};
+use aoc_core::report::{self, Format};
+use aoc_core::limits::Limited;
+use aoc_core::runner::RunResult;
+use aoc_core::{DynSolution, InputSource, Params, Part};
+use clap::Args;
+use tracing::info;
+
+use crate::days::Selection;
+use crate::params::ParamArgs;
+use crate::worker::LimitArgs;
+
+#[derive(Args)]
+pub struct RunArgs {
+    #[command(flatten)]
+    selection: Selection,
+
+    /// Output format: text, json, ndjson or junit
+    #[arg(long, default_value_t)]
+    format: Format,
+
+    #[command(flatten)]
+    limits: LimitArgs,
+
+    #[command(flatten)]
+    params: ParamArgs,
+}
+
+/// Runs the selected parts of a day.
+fn run_day(
+    limited: &Limited,
+    solution: &dyn DynSolution,
+    source: &InputSource,
+    params: Params,
+    parts: &[Part],
+) -> Vec<RunResult> {
+    match source.read(solution.year(), solution.day()) {
+        Ok(input) => limited.run(solution, &input.with_params(params), parts),
+        Err(e) => parts
+            .iter()
+            .map(|&part| RunResult::error(solution, part, &e))
+            .collect(),
+    }
+}
+
+pub fn run(args: &RunArgs) -> Result<()> {
+    let selection = &args.selection;
+    let source = selection.source();
+    let parts = selection.parts();
+    let limited = args.limits.limited()?;
+    let solutions = selection.solutions()?;
+    let params = args.params.load(&solutions)?;
+
+    let mut reporter = report::reporter(args.format, io::stdout());
+    let (mut succeeded, mut failures) = (0, 0);
+    for solution in solutions {
+        let day_params = params.get(solution.as_ref());
+        for result in run_day(&limited, solution.as_ref(), &source, day_params, &parts) {
+            if result.status.is_failure() {
+                failures += 1;
+            } else {
+                succeeded += 1;
+            }
+            reporter.report(&result)?;
+        }
+    }
+    reporter.finish()?;
+
+    info!("{} succeeded, {} failed", succeeded, failures);
+
+    if failures > 0 {
+        bail!("{} part(s) failed", failures);
+    }
+    Ok(())
+}
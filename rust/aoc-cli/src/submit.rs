@@ -0,0 +1,61 @@
+use anyhow::{bail, Result};
+use aoc_core::runner::{self, Status};
+use aoc_core::submit::Submitter;
+use aoc_core::{Answer, InputSource, Part};
+use clap::Args;
+use tracing::info;
+
+use crate::years;
+
+#[derive(Args)]
+pub struct SubmitArgs {
+    /// Puzzle year
+    year: u32,
+
+    /// Puzzle day
+    day: u32,
+
+    /// Part the answer is for
+    part: Part,
+
+    /// Answer to submit, computed from the day's input if omitted
+    answer: Option<String>,
+
+    /// Read the input from this file, or from stdin with `-`, instead of the default location
+    #[arg(long, value_name = "PATH", conflicts_with = "answer")]
+    input: Option<InputSource>,
+}
+
+fn compute(args: &SubmitArgs) -> Result<Answer> {
+    let Some(solution) = years::solutions(args.year)?
+        .into_iter()
+        .find(|s| s.day() == args.day)
+    else {
+        bail!("No solution for {} day {:02}", args.year, args.day);
+    };
+    let input = args
+        .input
+        .clone()
+        .unwrap_or_default()
+        .read(args.year, args.day)?;
+    let result = runner::run(solution.as_ref(), &input, &[args.part]).remove(0);
+    match (result.status, &result.answer) {
+        (Status::Ok, Some(answer)) => Ok(answer.clone()),
+        _ => bail!("{}: {}", result.label(), result.error.unwrap_or_default()),
+    }
+}
+
+pub fn submit(args: &SubmitArgs) -> Result<()> {
+    let answer = match &args.answer {
+        Some(answer) => Answer::from(answer.as_str()),
+        None => compute(args)?,
+    };
+    let submitter = Submitter::from_env()?;
+    let attempt = submitter.submit(args.year, args.day, args.part, &answer)?;
+    info!("{}", attempt.message);
+    println!(
+        "{} day {:02} part {}: {} is {}",
+        args.year, args.day, args.part, attempt.answer, attempt.verdict
+    );
+    Ok(())
+}
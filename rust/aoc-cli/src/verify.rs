@@ -0,0 +1,99 @@
+use std::io;
+
+use anyhow::{bail, Result};
+use aoc_core::answers::Answers;
+use aoc_core::input::is_not_found;
+use aoc_core::limits::Limited;
+use aoc_core::report::{self, Format};
+use aoc_core::runner::{RunResult, Status};
+use aoc_core::{check, examples, DynSolution, InputSource, Part};
+use clap::Args;
+use tracing::info;
+
+use crate::days::Days;
+use crate::worker::LimitArgs;
+use crate::years::{self, YEARS};
+
+#[derive(Args)]
+pub struct VerifyArgs {
+    /// Years to verify, all of them by default
+    years: Vec<u32>,
+
+    /// Only verify these days, e.g. `1..4`, `1..=4` or `1,3`
+    #[arg(long)]
+    days: Option<Days>,
+
+    /// Check the puzzle examples instead of the real inputs
+    #[arg(long)]
+    examples: bool,
+
+    /// Output format: text, json, ndjson or junit
+    #[arg(long, default_value_t)]
+    format: Format,
+
+    #[command(flatten)]
+    limits: LimitArgs,
+}
+
+/// Checks both parts of a day on its real input. A missing input makes both parts missing, while
+/// one that cannot be read makes them errors.
+fn verify_input(limited: &Limited, solution: &dyn DynSolution, answers: &Answers) -> Vec<RunResult> {
+    match InputSource::Default.read(solution.year(), solution.day()) {
+        Ok(input) => check::check_with(
+            limited,
+            solution,
+            &input,
+            &answers.day(solution.day()),
+            &Part::ALL,
+        ),
+        Err(e) if is_not_found(&e) => Part::ALL
+            .iter()
+            .map(|&part| RunResult::missing(solution, part, "no input"))
+            .collect(),
+        Err(e) => Part::ALL
+            .iter()
+            .map(|&part| RunResult::error(solution, part, &e))
+            .collect(),
+    }
+}
+
+pub fn verify(args: &VerifyArgs) -> Result<()> {
+    let years = if args.years.is_empty() {
+        YEARS.to_vec()
+    } else {
+        args.years.clone()
+    };
+
+    let limited = args.limits.limited()?;
+    let mut reporter = report::reporter(args.format, io::stdout());
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    for year in years {
+        let answers = Answers::load(year)?;
+        let solutions = years::solutions(year)?
+            .into_iter()
+            .filter(|s| args.days.as_ref().is_none_or(|days| days.contains(s.day())));
+        for solution in solutions {
+            let results = if args.examples {
+                examples::check_with(&limited, solution.as_ref())?
+            } else {
+                verify_input(&limited, solution.as_ref(), &answers)
+            };
+            for result in results {
+                match result.status {
+                    Status::Pass => passed += 1,
+                    Status::Missing => missing += 1,
+                    status if status.is_failure() => failed += 1,
+                    _ => {}
+                }
+                reporter.report(&result)?;
+            }
+        }
+    }
+    reporter.finish()?;
+
+    info!("{} passed, {} failed, {} missing", passed, failed, missing);
+    if failed > 0 {
+        bail!("{} part(s) did not match their expected answer", failed);
+    }
+    Ok(())
+}
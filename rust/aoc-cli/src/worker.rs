@@ -0,0 +1,75 @@
+use std::env;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use aoc_core::limits::{self, parse_duration, parse_size, Limited, Limits};
+use aoc_core::params::parse_assignment;
+use aoc_core::Part;
+use clap::Args;
+use tracing::level_filters::LevelFilter;
+
+use crate::years;
+
+/// Time and memory limits for each part, shared by the subcommands running solutions.
+#[derive(Args)]
+pub struct LimitArgs {
+    /// Stop a part after this long, e.g. `500ms`, `10s` or `2m`
+    ///
+    /// Without a memory limit, parts run in a thread of this process, and a part past the time
+    /// limit keeps running in the background, slowing down the parts after it. Add a memory limit
+    /// to run parts in a worker process, which is killed when it overruns.
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration)]
+    time_limit: Option<Duration>,
+
+    /// Stop a part using more memory than this, e.g. `512M` or `2G`
+    #[arg(long, value_name = "SIZE", value_parser = parse_size)]
+    memory_limit: Option<u64>,
+}
+
+impl LimitArgs {
+    /// Runs parts under the limits, in a worker thread or in an `aoc worker` process.
+    pub fn limited(&self) -> Result<Limited> {
+        let limited = Limited::new(Limits {
+            time: self.time_limit,
+            memory: self.memory_limit,
+        });
+        if self.memory_limit.is_none() {
+            return Ok(limited);
+        }
+        let exe = env::current_exe().context("Failed to locate the aoc executable")?;
+        let args: Vec<_> = log_flag().into_iter().chain(["worker"]).collect();
+        Ok(limited.with_worker(exe, &args))
+    }
+}
+
+/// The flag logging as much in a worker as in this process, whose stderr shows the worker's.
+fn log_flag() -> Option<&'static str> {
+    match LevelFilter::current() {
+        LevelFilter::OFF | LevelFilter::ERROR => Some("-qq"),
+        LevelFilter::WARN => Some("-q"),
+        LevelFilter::INFO => None,
+        LevelFilter::DEBUG => Some("-v"),
+        LevelFilter::TRACE => Some("-vv"),
+    }
+}
+
+#[derive(Args)]
+pub struct WorkerArgs {
+    year: u32,
+    day: u32,
+    #[arg(required = true)]
+    parts: Vec<Part>,
+    #[arg(long)]
+    memory_limit: Option<u64>,
+    #[arg(long = "param", value_parser = parse_assignment)]
+    params: Vec<(String, String)>,
+}
+
+pub fn worker(args: &WorkerArgs) -> Result<()> {
+    let solution = years::solutions(args.year)?
+        .into_iter()
+        .find(|s| s.day() == args.day)
+        .with_context(|| format!("No solution for {} day {}", args.year, args.day))?;
+    let params = args.params.iter().cloned().collect();
+    limits::serve_worker(solution.as_ref(), &args.parts, params, args.memory_limit)
+}
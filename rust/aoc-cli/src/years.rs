@@ -0,0 +1,13 @@
+use anyhow::{bail, Result};
+use aoc_core::DynSolution;
+
+pub const YEARS: &[u32] = &[2020, 2021, 2023];
+
+pub fn solutions(year: u32) -> Result<Vec<Box<dyn DynSolution>>> {
+    match year {
+        2020 => Ok(aoc_2020::solutions()),
+        2021 => Ok(aoc_2021::solutions()),
+        2023 => Ok(aoc_2023::solutions()),
+        _ => bail!("No solutions for year {}, available years: {:?}", year, YEARS),
+    }
+}
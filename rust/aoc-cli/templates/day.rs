@@ -0,0 +1,74 @@
+use anyhow::{bail, Result};
+use aoc_core::{Input, ParseError, Solution};
+
+pub fn parse(input: &Input) -> Result<Vec<u32>, ParseError> {
+    ParseError::finish(input, parser::numbers(input))
+}
+
+pub fn part1(_numbers: &[u32]) -> Result<u32> {
+    bail!("Part 1 is not solved yet")
+}
+
+pub fn part2(_numbers: &[u32]) -> Result<u32> {
+    bail!("Part 2 is not solved yet")
+}
+
+mod parser {
+    use aoc_parse::lines;
+    use nom::{character::complete::u32, IResult};
+
+    pub fn numbers(input: &str) -> IResult<&str, Vec<u32>> {
+        lines(u32)(input)
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        #[test]
+        fn test_numbers() {
+            assert_eq!(numbers("1\n2\n3"), Ok(("", vec![1, 2, 3])));
+        }
+    }
+}
+
+pub struct Day{{DD}};
+
+impl Solution for Day{{DD}} {
+    const YEAR: u32 = {{YEAR}};
+    const DAY: u32 = {{DAY}};
+
+    type Model = Vec<u32>;
+    type Answer = u32;
+
+    fn parse(input: &Input) -> Result<Vec<u32>> {
+        Ok(parse(input)?)
+    }
+
+    fn part1(model: &Vec<u32>) -> Result<u32> {
+        part1(model)
+    }
+
+    fn part2(model: &Vec<u32>) -> Result<u32> {
+        part2(model)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TEST_DATA: &str = include_str!("../data/day_{{DD}}/example_1.txt");
+
+    #[test]
+    #[ignore = "part 1 is not solved yet"]
+    fn test_part1() {
+        assert_eq!(part1(&parse(&Input::new(TEST_DATA)).unwrap()).unwrap(), 0);
+    }
+
+    #[test]
+    #[ignore = "part 2 is not solved yet"]
+    fn test_part2() {
+        assert_eq!(part2(&parse(&Input::new(TEST_DATA)).unwrap()).unwrap(), 0);
+    }
+}
@@ -0,0 +1,187 @@
+use std::fmt::{self, Display};
+
+use num_bigint::BigInt;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The answer of a part.
+///
+/// Answers compare by value: integers are equal whatever their variant, and a string is equal to
+/// an integer when it is that integer written out, so `Int(42)`, `Big(42)` and `Str("42")` all
+/// match. Strings that are not plain integers, e.g. `"007"`, only match the same string.
+#[derive(Clone, Debug)]
+pub enum Answer {
+    Int(i64),
+    UInt(u64),
+    Big(BigInt),
+    Str(String),
+}
+
+/// Parses an integer written the way it displays: digits with an optional leading `-`, and no
+/// leading zeros.
+fn parse_int(s: &str) -> Option<BigInt> {
+    let digits = s.strip_prefix('-').unwrap_or(s);
+    let canonical = match digits.as_bytes() {
+        [] => false,
+        [b'0'] => digits.len() == s.len(),
+        [first, rest @ ..] => {
+            (b'1'..=b'9').contains(first) && rest.iter().all(u8::is_ascii_digit)
+        }
+    };
+    canonical.then(|| s.parse().ok()).flatten()
+}
+
+impl Answer {
+    /// The answer as an integer, if it is one or is a string holding one.
+    pub fn to_int(&self) -> Option<BigInt> {
+        match self {
+            Answer::Int(n) => Some((*n).into()),
+            Answer::UInt(n) => Some((*n).into()),
+            Answer::Big(n) => Some(n.clone()),
+            Answer::Str(s) => parse_int(s),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.to_int(), other.to_int()) {
+            (Some(a), Some(b)) => a == b,
+            (None, None) => self.to_string() == other.to_string(),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::UInt(n) => write!(f, "{}", n),
+            Answer::Big(n) => write!(f, "{}", n),
+            Answer::Str(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! from_int {
+    ($variant:ident as $wide:ty: $($t:ty),*) => {$(
+        impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Answer::$variant(n as $wide)
+            }
+        }
+    )*};
+}
+
+from_int!(Int as i64: i8, i16, i32, i64, isize);
+from_int!(UInt as u64: u8, u16, u32, u64, usize);
+
+impl From<i128> for Answer {
+    fn from(n: i128) -> Self {
+        i64::try_from(n).map_or_else(|_| Answer::Big(n.into()), Answer::Int)
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        u64::try_from(n).map_or_else(|_| Answer::Big(n.into()), Answer::UInt)
+    }
+}
+
+impl From<BigInt> for Answer {
+    fn from(n: BigInt) -> Self {
+        Answer::Big(n)
+    }
+}
+
+/// A string is trimmed, and kept as an integer if it is one.
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        let s = s.trim();
+        match parse_int(s) {
+            Some(n) => match (i64::try_from(&n), u64::try_from(&n)) {
+                (Ok(n), _) => Answer::Int(n),
+                (_, Ok(n)) => Answer::UInt(n),
+                _ => Answer::Big(n),
+            },
+            None => Answer::Str(s.to_string()),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        s.as_str().into()
+    }
+}
+
+/// Serialized as a string, so integers of any size survive JSON.
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Deserialized from an integer or a string, e.g. `part1 = 42` or `part1 = "42"` in TOML.
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Int(i64),
+            UInt(u64),
+            Str(String),
+        }
+
+        Ok(match Raw::deserialize(deserializer)? {
+            Raw::Int(n) => Answer::Int(n),
+            Raw::UInt(n) => Answer::UInt(n),
+            Raw::Str(s) => s.into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_eq() {
+        assert_eq!(Answer::Int(42), Answer::UInt(42));
+        assert_eq!(Answer::Big(42.into()), Answer::Str("42".to_string()));
+        assert_eq!(Answer::Int(-7), Answer::Str("-7".to_string()));
+        assert_ne!(Answer::Int(7), Answer::Str("007".to_string()));
+        assert_ne!(Answer::Int(0), Answer::Str("-0".to_string()));
+        assert_ne!(Answer::Int(1), Answer::Int(2));
+        assert_eq!(Answer::from("  abc "), Answer::Str("abc".to_string()));
+        assert_ne!(Answer::from("abc"), Answer::from("ABC"));
+    }
+
+    #[test]
+    fn test_from() {
+        assert!(matches!(Answer::from(3u32), Answer::UInt(3)));
+        assert!(matches!(Answer::from(-3i32), Answer::Int(-3)));
+        assert!(matches!(Answer::from(u64::MAX as u128 + 1), Answer::Big(_)));
+        assert!(matches!(Answer::from("12"), Answer::Int(12)));
+        assert!(matches!(Answer::from(u64::MAX.to_string()), Answer::UInt(u64::MAX)));
+        assert!(matches!(Answer::from("123456789012345678901234567890"), Answer::Big(_)));
+        assert!(matches!(Answer::from("007"), Answer::Str(_)));
+        assert_eq!(
+            Answer::from(u128::MAX).to_string(),
+            "340282366920938463463374607431768211455"
+        );
+    }
+
+    #[test]
+    fn test_serde() {
+        assert_eq!(serde_json::to_string(&Answer::UInt(5)).unwrap(), r#""5""#);
+        let answer: Answer = serde_json::from_str(r#""18446744073709551616""#).unwrap();
+        assert_eq!(answer, Answer::from(1u128 << 64));
+        let answer: Answer = serde_json::from_str("-3").unwrap();
+        assert!(matches!(answer, Answer::Int(-3)));
+        let answer: Answer = serde_json::from_str(r#""LEFT""#).unwrap();
+        assert!(matches!(answer, Answer::Str(s) if s == "LEFT"));
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use anyhow::{bail, Context, Error, Result};
+use serde::Deserialize;
+
+use crate::input::data_dirs;
+use crate::{Answer, Part};
+
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// Expected answers for the parts of one input, as a `part1`/`part2` TOML table.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Expected {
+    part1: Option<Answer>,
+    part2: Option<Answer>,
+}
+
+impl Expected {
+    pub fn get(&self, part: Part) -> Option<Answer> {
+        match part {
+            Part::One => self.part1.clone(),
+            Part::Two => self.part2.clone(),
+        }
+    }
+
+    /// The parts that have an expected answer.
+    pub fn parts(&self) -> Vec<Part> {
+        Part::ALL
+            .into_iter()
+            .filter(|&part| self.get(part).is_some())
+            .collect()
+    }
+}
+
+/// Known answers of a year, keyed by day and part.
+///
+/// The file has one table per day:
+///
+/// ```toml
+/// [day_01]
+/// part1 = 55776
+/// part2 = "223162626"
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Answers {
+    days: BTreeMap<u32, Expected>,
+}
+
+impl Answers {
+    /// Loads the answers of a year from the first data directory that has an answers file.
+    /// A year without one has no known answers.
+    pub fn load(year: u32) -> Result<Self> {
+        match path(year) {
+            Some(path) => fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?
+                .parse()
+                .with_context(|| format!("Invalid answers file {}", path.display())),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// The expected answers of a day, empty if none are known.
+    pub fn day(&self, day: u32) -> Expected {
+        self.days.get(&day).cloned().unwrap_or_default()
+    }
+
+    pub fn get(&self, day: u32, part: Part) -> Option<Answer> {
+        self.days.get(&day)?.get(part)
+    }
+}
+
+pub(crate) fn parse_day_key(key: &str) -> Result<u32> {
+    match key.strip_prefix("day_").map(str::parse) {
+        Some(Ok(day)) => Ok(day),
+        _ => bail!("Invalid day key {:?}, expected e.g. day_01", key),
+    }
+}
+
+impl FromStr for Answers {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let entries: BTreeMap<String, Expected> = toml::from_str(s)?;
+        let days = entries
+            .into_iter()
+            .map(|(key, expected)| Ok((parse_day_key(&key)?, expected)))
+            .collect::<Result<_>>()?;
+        Ok(Self { days })
+    }
+}
+
+/// The answers file of a year, if any of its data directories has one.
+pub fn path(year: u32) -> Option<PathBuf> {
+    data_dirs(year)
+        .into_iter()
+        .map(|dir| dir.join(ANSWERS_FILE))
+        .find(|p| p.exists())
+}
+
+/// The known answer for a part, if any.
+pub fn expected(year: u32, day: u32, part: Part) -> Option<Answer> {
+    Answers::load(year).ok()?.get(day, part)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const ANSWERS: &str = r#"
+        [day_01]
+        part1 = 55776
+        part2 = "223162626"
+
+        [day_03]
+        part1 = 162
+    "#;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = ANSWERS.parse::<Answers>().unwrap();
+        assert_eq!(answers.get(1, Part::One), Some(Answer::Int(55776)));
+        assert_eq!(answers.get(1, Part::Two), Some(Answer::Int(223162626)));
+        assert_eq!(answers.get(3, Part::One), Some("162".into()));
+        assert_eq!(answers.get(3, Part::Two), None);
+        assert_eq!(answers.get(2, Part::One), None);
+        assert_eq!(answers.day(3).parts(), vec![Part::One]);
+    }
+
+    #[test]
+    fn test_invalid_answers() {
+        assert!("[one]\npart1 = 1".parse::<Answers>().is_err());
+        assert!("[day_01]\npart3 = 1".parse::<Answers>().is_err());
+    }
+}
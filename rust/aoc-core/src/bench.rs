@@ -0,0 +1,145 @@
+use std::fmt::{self, Display};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+
+use crate::{DynSolution, Input, Part};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BenchOptions {
+    /// Untimed runs before measuring.
+    pub warmup: usize,
+    /// Timed runs.
+    pub iterations: usize,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            warmup: 3,
+            iterations: 20,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Stage {
+    Parse,
+    Solve(Part),
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Solve(part) => write!(f, "part {}", part),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub iterations: usize,
+    pub mean: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    /// Summarizes a non-empty set of timings.
+    pub fn new(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "No samples to summarize");
+        samples.sort();
+        let n = samples.len();
+        let mean = samples.iter().sum::<Duration>() / n as u32;
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        } else {
+            samples[n / 2]
+        };
+        // Nearest-rank percentile
+        let p95 = samples[(n * 95).div_ceil(100) - 1];
+        Self {
+            iterations: n,
+            mean,
+            median,
+            p95,
+        }
+    }
+}
+
+fn measure<T>(options: &BenchOptions, mut f: impl FnMut() -> Result<T>) -> Result<Stats> {
+    for _ in 0..options.warmup {
+        black_box(f()?);
+    }
+    let samples = (0..options.iterations.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f()?);
+            Ok(start.elapsed())
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Stats::new(samples))
+}
+
+/// Times the parse step and each requested part of a day separately.
+pub fn bench(
+    solution: &dyn DynSolution,
+    input: &Input,
+    parts: &[Part],
+    options: &BenchOptions,
+) -> Result<Vec<(Stage, Stats)>> {
+    input.params().resolve(solution.params())?;
+    let mut results = vec![(Stage::Parse, measure(options, || solution.parse(input))?)];
+    let model = solution.parse(input)?;
+    for &part in parts {
+        let stats = measure(options, || solution.solve(model.as_ref(), part))?;
+        results.push((Stage::Solve(part), stats));
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ms(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&v| Duration::from_millis(v)).collect()
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::new(ms(&[5, 1, 3, 2, 4]));
+        assert_eq!(stats.iterations, 5);
+        assert_eq!(stats.mean, Duration::from_millis(3));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.p95, Duration::from_millis(5));
+
+        let stats = Stats::new(ms(&[4, 1, 3, 2]));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+    }
+
+    #[test]
+    fn test_p95() {
+        let stats = Stats::new(ms(&(1..=100).collect::<Vec<_>>()));
+        assert_eq!(stats.p95, Duration::from_millis(95));
+        assert_eq!(stats.median, Duration::from_micros(50500));
+    }
+
+    #[test]
+    fn test_measure() {
+        let mut calls = 0;
+        let options = BenchOptions {
+            warmup: 2,
+            iterations: 5,
+        };
+        let stats = measure(&options, || {
+            calls += 1;
+            Ok(calls)
+        })
+        .unwrap();
+        assert_eq!(calls, 7);
+        assert_eq!(stats.iterations, 5);
+    }
+}
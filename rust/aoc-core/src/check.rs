@@ -0,0 +1,57 @@
+use crate::answers::Expected;
+use crate::limits::Limited;
+use crate::runner::RunResult;
+use crate::{DynSolution, Input, Part};
+
+/// Runs each of `parts` on `input` and compares the answers with `expected`.
+pub fn check(
+    solution: &dyn DynSolution,
+    input: &Input,
+    expected: &Expected,
+    parts: &[Part],
+) -> Vec<RunResult> {
+    check_with(&Limited::default(), solution, input, expected, parts)
+}
+
+/// Like [`check`], running the parts under limits.
+pub fn check_with(
+    limited: &Limited,
+    solution: &dyn DynSolution,
+    input: &Input,
+    expected: &Expected,
+    parts: &[Part],
+) -> Vec<RunResult> {
+    limited
+        .run(solution, input, parts)
+        .into_iter()
+        .map(|result| {
+            let expected = expected.get(result.part);
+            result.check(expected)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::runner::Status;
+    use crate::testing::{statuses, Sum};
+    use crate::{solution, Answer};
+
+    #[test]
+    fn test_check() {
+        let s = solution::<Sum>();
+        let expected = toml::from_str::<Expected>("part1 = 10\npart2 = 25").unwrap();
+        let results = check(s.as_ref(), &Input::new("1,2,3,4"), &expected, &Part::ALL);
+        assert_eq!(statuses(&results), vec![Status::Pass, Status::Fail]);
+        assert_eq!(results[1].answer, Some(Answer::UInt(24)));
+        assert_eq!(results[1].expected, Some(Answer::UInt(25)));
+
+        let expected = toml::from_str::<Expected>("part2 = 24").unwrap();
+        let results = check(s.as_ref(), &Input::new("1,2,3,4"), &expected, &Part::ALL);
+        assert_eq!(statuses(&results), vec![Status::Missing, Status::Pass]);
+
+        let results = check(s.as_ref(), &Input::new("1,x"), &expected, &Part::ALL);
+        assert_eq!(statuses(&results), vec![Status::Error, Status::Error]);
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+use crate::answers::Expected;
+use crate::check;
+use crate::input::{workspace_data_dir, Input};
+use crate::limits::Limited;
+use crate::runner::RunResult;
+use crate::DynSolution;
+
+pub const EXAMPLES_FILE: &str = "examples.toml";
+
+/// A puzzle example, stored as `<name>.txt` next to the day's `examples.toml`:
+///
+/// ```toml
+/// [example_1]
+/// part1 = 4361
+/// part2 = 467835
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Example {
+    pub name: String,
+    pub input: Input,
+    pub expected: Expected,
+}
+
+impl Example {
+    /// Checks the parts this example has an answer for.
+    pub fn check(&self, solution: &dyn DynSolution) -> Vec<RunResult> {
+        self.check_with(&Limited::default(), solution)
+    }
+
+    /// Like [`Example::check`], running the parts under limits.
+    pub fn check_with(&self, limited: &Limited, solution: &dyn DynSolution) -> Vec<RunResult> {
+        let parts = self.expected.parts();
+        check::check_with(limited, solution, &self.input, &self.expected, &parts)
+            .into_iter()
+            .map(|result| RunResult {
+                example: Some(self.name.clone()),
+                ..result
+            })
+            .collect()
+    }
+}
+
+/// Directory holding the examples of a day. Examples are part of the repository, so unlike
+/// inputs they are always read from the workspace.
+pub fn dir(year: u32, day: u32) -> Result<PathBuf> {
+    Ok(workspace_data_dir(year)?.join(format!("day_{:02}", day)))
+}
+
+/// Loads the examples of a day, in name order. A day without an examples file has none.
+pub fn load(year: u32, day: u32) -> Result<Vec<Example>> {
+    let dir = dir(year, day)?;
+    let path = dir.join(EXAMPLES_FILE);
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let content =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let entries: BTreeMap<String, Expected> = toml::from_str(&content)
+        .with_context(|| format!("Invalid examples file {}", path.display()))?;
+    entries
+        .into_iter()
+        .map(|(name, expected)| {
+            let path = dir.join(format!("{}.txt", name));
+            let input = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let input = Input::new(&input);
+            Ok(Example {
+                name,
+                input,
+                expected,
+            })
+        })
+        .collect()
+}
+
+/// Checks every example of a day.
+pub fn check(solution: &dyn DynSolution) -> Result<Vec<RunResult>> {
+    check_with(&Limited::default(), solution)
+}
+
+/// Like [`check`], running the parts under limits.
+pub fn check_with(limited: &Limited, solution: &dyn DynSolution) -> Result<Vec<RunResult>> {
+    Ok(load(solution.year(), solution.day())?
+        .iter()
+        .flat_map(|example| example.check_with(limited, solution))
+        .collect())
+}
@@ -0,0 +1,398 @@
+use std::env;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{bail, Context, Result};
+
+/// Environment variable holding the adventofcode.com session cookie.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+/// Environment variable overriding the directory downloaded inputs are cached in.
+pub const CACHE_DIR_VAR: &str = "AOC_CACHE_DIR";
+/// Environment variable overriding the site used for inputs and answers, e.g. a local stand-in.
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = concat!("aoc-core/", env!("CARGO_PKG_VERSION"));
+const LAST_REQUEST_FILE: &str = ".last_request";
+
+/// Minimum time between two requests to the site, also across runs.
+pub const MIN_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Minimal HTTP backend, so the site can be replaced by something else in tests.
+pub trait HttpClient {
+    fn get(&self, url: &str, session: &str) -> Result<Response>;
+    fn post(&self, url: &str, session: &str, form: &[(&str, &str)]) -> Result<Response>;
+}
+
+/// The default backend.
+pub struct UreqClient(ureq::Agent);
+
+impl Default for UreqClient {
+    fn default() -> Self {
+        Self(
+            ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+        )
+    }
+}
+
+fn into_response(url: &str, result: Result<ureq::Response, ureq::Error>) -> Result<Response> {
+    let response = match result {
+        Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+        Err(e) => return Err(e).with_context(|| format!("Request to {} failed", url)),
+    };
+    let status = response.status();
+    let mut body = String::new();
+    response
+        .into_reader()
+        .read_to_string(&mut body)
+        .with_context(|| format!("Failed to read the response from {}", url))?;
+    Ok(Response { status, body })
+}
+
+impl HttpClient for UreqClient {
+    fn get(&self, url: &str, session: &str) -> Result<Response> {
+        let cookie = format!("session={}", session);
+        into_response(url, self.0.get(url).set("Cookie", &cookie).call())
+    }
+
+    fn post(&self, url: &str, session: &str, form: &[(&str, &str)]) -> Result<Response> {
+        let cookie = format!("session={}", session);
+        into_response(url, self.0.post(url).set("Cookie", &cookie).send_form(form))
+    }
+}
+
+fn home_subdir(xdg_var: &str, fallback: &str) -> Option<PathBuf> {
+    match env::var_os(xdg_var) {
+        Some(dir) if !dir.is_empty() => Some(PathBuf::from(dir)),
+        _ => env::var_os("HOME").map(|home| Path::new(&home).join(fallback)),
+    }
+}
+
+/// Directory downloaded inputs are cached in, laid out as `<year>/day_XX/input.txt`:
+/// `$AOC_CACHE_DIR`, or `aoc` in the user cache directory.
+pub fn cache_dir() -> Option<PathBuf> {
+    match env::var_os(CACHE_DIR_VAR) {
+        Some(dir) if !dir.is_empty() => Some(PathBuf::from(dir)),
+        _ => home_subdir("XDG_CACHE_HOME", ".cache").map(|dir| dir.join("aoc")),
+    }
+}
+
+/// Configuration directory, `aoc` in the user configuration directory.
+pub fn config_dir() -> Option<PathBuf> {
+    home_subdir("XDG_CONFIG_HOME", ".config").map(|dir| dir.join("aoc"))
+}
+
+/// The session token, from `$AOC_SESSION` or else the `session` file of the configuration
+/// directory.
+pub fn session() -> Result<Option<String>> {
+    if let Ok(session) = env::var(SESSION_VAR) {
+        if !session.trim().is_empty() {
+            return Ok(Some(session.trim().to_string()));
+        }
+    }
+    let Some(path) = config_dir().map(|dir| dir.join("session")) else {
+        return Ok(None);
+    };
+    if !path.exists() {
+        return Ok(None);
+    }
+    let session =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(Some(session.trim().to_string()).filter(|s| !s.is_empty()))
+}
+
+/// Downloads puzzle inputs into a local cache. An input is only ever downloaded once.
+pub struct Fetcher {
+    client: Box<dyn HttpClient>,
+    base_url: String,
+    session: Option<String>,
+    cache_dir: PathBuf,
+    min_interval: Duration,
+}
+
+impl Fetcher {
+    pub fn new(client: impl HttpClient + 'static, cache_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            client: Box::new(client),
+            base_url: BASE_URL.to_string(),
+            session: None,
+            cache_dir: cache_dir.into(),
+            min_interval: MIN_INTERVAL,
+        }
+    }
+
+    /// A fetcher using the default backend, configured from the environment.
+    pub fn from_env() -> Result<Self> {
+        let Some(cache_dir) = cache_dir() else {
+            bail!("No cache directory, set {} or HOME", CACHE_DIR_VAR);
+        };
+        let mut fetcher = Self::new(UreqClient::default(), cache_dir);
+        fetcher.session = session()?;
+        if let Ok(url) = env::var(BASE_URL_VAR) {
+            fetcher = fetcher.with_base_url(url);
+        }
+        Ok(fetcher)
+    }
+
+    pub fn with_base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_session(mut self, session: impl Into<String>) -> Self {
+        self.session = Some(session.into());
+        self
+    }
+
+    pub fn with_min_interval(mut self, interval: Duration) -> Self {
+        self.min_interval = interval;
+        self
+    }
+
+    pub fn has_session(&self) -> bool {
+        self.session.is_some()
+    }
+
+    pub fn cache_path(&self, year: u32, day: u32) -> PathBuf {
+        self.cache_dir
+            .join(year.to_string())
+            .join(format!("day_{:02}", day))
+            .join("input.txt")
+    }
+
+    /// The cached input of a day, if it was downloaded before.
+    pub fn cached(&self, year: u32, day: u32) -> Option<PathBuf> {
+        Some(self.cache_path(year, day)).filter(|path| path.exists())
+    }
+
+    /// Returns the input of a day, downloading it only if it is not cached yet.
+    pub fn fetch(&self, year: u32, day: u32) -> Result<String> {
+        let path = self.cache_path(year, day);
+        if path.exists() {
+            return fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()));
+        }
+        let Some(session) = &self.session else {
+            bail!(
+                "No session token to download {} day {:02}, set {}",
+                year,
+                day,
+                SESSION_VAR
+            );
+        };
+
+        wait_turn(&self.cache_dir, self.min_interval)?;
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        let response = self.client.get(&url, session)?;
+        match response.status {
+            200 => {}
+            400 => bail!("The session token was rejected by {}", self.base_url),
+            404 => bail!("The input of {} day {:02} is not available yet", year, day),
+            status => bail!("Unexpected status {} from {}", status, url),
+        }
+
+        let dir = path.parent().unwrap();
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        // Write to a temporary file first so an interrupted run never leaves a partial input.
+        let partial = path.with_extension("txt.partial");
+        fs::write(&partial, &response.body)
+            .and_then(|_| fs::rename(&partial, &path))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(response.body)
+    }
+}
+
+/// Sleeps until at least `min_interval` has passed since the last request to the site, which is
+/// recorded in `cache_dir`.
+pub(crate) fn wait_turn(cache_dir: &Path, min_interval: Duration) -> Result<()> {
+    let stamp = cache_dir.join(LAST_REQUEST_FILE);
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+    let last = fs::read_to_string(&stamp)
+        .ok()
+        .and_then(|s| s.trim().parse::<u64>().ok())
+        .map(Duration::from_nanos);
+    if let Some(elapsed) = last.map(|last| now.saturating_sub(last)) {
+        if elapsed < min_interval {
+            thread::sleep(min_interval - elapsed);
+        }
+    }
+    fs::create_dir_all(cache_dir)
+        .with_context(|| format!("Failed to create {}", cache_dir.display()))?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+    fs::write(&stamp, now.as_nanos().to_string())
+        .with_context(|| format!("Failed to write {}", stamp.display()))
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use std::cell::RefCell;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::rc::Rc;
+    use std::time::Instant;
+
+    use super::*;
+
+    /// Answers every request with the same response, recording the requested URLs.
+    #[derive(Clone)]
+    struct FakeClient {
+        response: Response,
+        urls: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl FakeClient {
+        fn new(status: u16, body: &str) -> Self {
+            Self {
+                response: Response {
+                    status,
+                    body: body.to_string(),
+                },
+                urls: Rc::default(),
+            }
+        }
+    }
+
+    impl HttpClient for FakeClient {
+        fn get(&self, url: &str, session: &str) -> Result<Response> {
+            assert_eq!(session, "secret");
+            self.urls.borrow_mut().push(url.to_string());
+            Ok(self.response.clone())
+        }
+
+        fn post(&self, _: &str, _: &str, _: &[(&str, &str)]) -> Result<Response> {
+            unreachable!("inputs are only ever downloaded")
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-fetch-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn fetcher(client: &FakeClient, dir: &Path) -> Fetcher {
+        Fetcher::new(client.clone(), dir)
+            .with_session("secret")
+            .with_min_interval(Duration::ZERO)
+    }
+
+    #[test]
+    fn test_fetch_once() {
+        let dir = temp_dir("once");
+        let client = FakeClient::new(200, "1\n2\n3\n");
+        let fetcher = fetcher(&client, &dir);
+        assert_eq!(fetcher.cached(2020, 1), None);
+        assert_eq!(fetcher.fetch(2020, 1).unwrap(), "1\n2\n3\n");
+        assert_eq!(fetcher.fetch(2020, 1).unwrap(), "1\n2\n3\n");
+        assert_eq!(
+            *client.urls.borrow(),
+            vec!["https://adventofcode.com/2020/day/1/input"]
+        );
+        assert_eq!(
+            fetcher.cached(2020, 1),
+            Some(dir.join("2020/day_01/input.txt"))
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_errors() {
+        let dir = temp_dir("errors");
+        let client = FakeClient::new(404, "Not Found");
+        let err = fetcher(&client, &dir).fetch(2023, 25).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The input of 2023 day 25 is not available yet"
+        );
+        assert_eq!(fetcher(&client, &dir).cached(2023, 25), None);
+
+        let no_session = Fetcher::new(client.clone(), &dir);
+        assert!(no_session.fetch(2023, 1).is_err());
+        assert_eq!(client.urls.borrow().len(), 1);
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let dir = temp_dir("rate");
+        let client = FakeClient::new(200, "input");
+        let interval = Duration::from_millis(200);
+        let start = Instant::now();
+        fetcher(&client, &dir)
+            .with_min_interval(interval)
+            .fetch(2021, 1)
+            .unwrap();
+        // A new fetcher still waits, as the last request is recorded on disk.
+        fetcher(&client, &dir)
+            .with_min_interval(interval)
+            .fetch(2021, 2)
+            .unwrap();
+        assert!(start.elapsed() >= interval);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    /// Serves a single request on a local port with `body`, returning the base URL and a handle
+    /// yielding the request's head lines and body.
+    pub(crate) fn serve_once(
+        body: &'static str,
+    ) -> (String, thread::JoinHandle<(Vec<String>, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut head = vec![];
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                head.push(line.trim().to_string());
+            }
+            let length = head
+                .iter()
+                .find_map(|h| h.to_lowercase().strip_prefix("content-length: ")?.parse().ok())
+                .unwrap_or(0);
+            let mut request_body = vec![0; length];
+            reader.read_exact(&mut request_body).unwrap();
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            (head, String::from_utf8(request_body).unwrap())
+        });
+        (url, server)
+    }
+
+    #[test]
+    fn test_ureq_client() {
+        let (url, server) = serve_once("light red bags contain 1 bright white bag.");
+        let dir = temp_dir("ureq");
+        let input = Fetcher::new(UreqClient::default(), &dir)
+            .with_base_url(url)
+            .with_session("secret")
+            .fetch(2020, 7)
+            .unwrap();
+        assert_eq!(input, "light red bags contain 1 bright white bag.");
+        let (head, _) = server.join().unwrap();
+        assert_eq!(head[0], "GET /2020/day/7/input HTTP/1.1");
+        assert!(head.iter().any(|h| h == "Cookie: session=secret"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,376 @@
+use std::env;
+use std::fmt::{self, Display};
+use std::fs;
+use std::io::{self, Read};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Context, Error, Result};
+
+use crate::fetch::{self, Fetcher};
+use crate::params::{Param, Params};
+use crate::parse::ParseError;
+
+/// Environment variable pointing at a directory laid out as `<year>/day_XX/input.txt`.
+pub const DATA_DIR_VAR: &str = "AOC_DATA_DIR";
+
+/// The text of a puzzle input, normalized so that line endings are `\n` and no line, nor the
+/// input, ends in whitespace. It derefs to `str` and offers views borrowing from it.
+///
+/// It also carries the puzzle parameters given for the run, see [`Input::param`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Input {
+    text: String,
+    params: Params,
+}
+
+impl Input {
+    pub fn new(text: &str) -> Self {
+        let text = text.lines().map(str::trim_end).collect::<Vec<_>>().join("\n");
+        Self {
+            text: text.trim_end().to_string(),
+            params: Params::new(),
+        }
+    }
+
+    /// The input with parameter values overriding the defaults of the day.
+    pub fn with_params(self, params: Params) -> Self {
+        Self { params, ..self }
+    }
+
+    /// The parameter values given for the run, without the defaults.
+    pub fn params(&self) -> &Params {
+        &self.params
+    }
+
+    /// The value of `param`: the one given for the run, or its default.
+    pub fn param<T>(&self, param: &Param) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let value = self.params.get(param.name).unwrap_or(param.default);
+        value
+            .parse()
+            .map_err(|e| anyhow!("Invalid parameter {}={:?}: {}", param.name, value, e))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// The lines of the input; an empty input has none.
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.text.lines()
+    }
+
+    /// Paragraphs separated by one or more blank lines.
+    pub fn blocks(&self) -> impl Iterator<Item = &str> {
+        self.text
+            .split("\n\n")
+            .map(|block| block.trim_matches('\n'))
+            .filter(|block| !block.is_empty())
+    }
+
+    /// Whitespace-separated tokens.
+    pub fn tokens(&self) -> impl Iterator<Item = &str> {
+        self.text.split_whitespace()
+    }
+
+    /// Parses every token as a number.
+    pub fn ints<T>(&self) -> Result<Vec<T>, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.tokens()
+            .map(|token| {
+                token
+                    .parse()
+                    .map_err(|e| self.error(token, format!("invalid number {:?}: {}", token, e)))
+            })
+            .collect()
+    }
+
+    /// The lines as rows of bytes, which must all be non-empty and of the same width.
+    pub fn grid(&self) -> Result<Vec<&[u8]>, ParseError> {
+        let mut width = None;
+        self.lines()
+            .map(|line| {
+                let width = *width.get_or_insert(line.len());
+                if line.is_empty() {
+                    return Err(self.error(line, "empty grid row"));
+                }
+                if line.len() != width {
+                    let at = &line[line.len().min(width)..];
+                    return Err(self.error(at, format!("expected rows of {} cells", width)));
+                }
+                Ok(line.as_bytes())
+            })
+            .collect()
+    }
+
+    /// An error at `at`, which must be a slice of the input such as a line or token.
+    pub fn error(&self, at: &str, message: impl Display) -> ParseError {
+        ParseError::at_str(&self.text, at, message)
+    }
+}
+
+impl Deref for Input {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.text
+    }
+}
+
+impl Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl From<&str> for Input {
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+}
+
+/// Where a day's puzzle input is read from.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum InputSource {
+    /// Resolved from the year and day, see [`default_path`]. A missing input is downloaded
+    /// into the cache when a session token is configured.
+    #[default]
+    Default,
+    Stdin,
+    Path(PathBuf),
+}
+
+impl FromStr for InputSource {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "" => bail!("Empty input path"),
+            "-" => Ok(InputSource::Stdin),
+            _ => Ok(InputSource::Path(PathBuf::from(s))),
+        }
+    }
+}
+
+impl InputSource {
+    pub fn read(&self, year: u32, day: u32) -> Result<Input> {
+        self.read_raw(year, day).map(|text| Input::new(&text))
+    }
+
+    fn read_raw(&self, year: u32, day: u32) -> Result<String> {
+        match self {
+            InputSource::Default => read_default(year, day),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .context("Failed to read input from stdin")?;
+                Ok(input)
+            }
+            InputSource::Path(path) => read_file(path),
+        }
+    }
+}
+
+fn read_file(path: &Path) -> Result<String> {
+    fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))
+}
+
+fn read_default(year: u32, day: u32) -> Result<String> {
+    let missing = match default_path(year, day) {
+        Ok(path) => return read_file(&path),
+        Err(e) => e,
+    };
+    match Fetcher::from_env() {
+        Ok(fetcher) if fetcher.has_session() => fetcher.fetch(year, day),
+        _ => Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{}; set {} to download it", missing, fetch::SESSION_VAR),
+        )
+        .into()),
+    }
+}
+
+/// Whether reading an input failed because there is none, as opposed to an unreadable file or a
+/// failed download.
+pub fn is_not_found(err: &Error) -> bool {
+    err.chain()
+        .filter_map(|cause| cause.downcast_ref::<io::Error>())
+        .any(|e| e.kind() == io::ErrorKind::NotFound)
+}
+
+fn day_path(dir: &Path, day: u32) -> PathBuf {
+    dir.join(format!("day_{:02}", day)).join("input.txt")
+}
+
+/// Data directories of a year, in order of precedence: `$AOC_DATA_DIR/<year>`, then the `data`
+/// directory of the year crate in the workspace, if one is found.
+pub fn data_dirs(year: u32) -> Vec<PathBuf> {
+    let mut dirs = vec![];
+    if let Some(dir) = env::var_os(DATA_DIR_VAR) {
+        dirs.push(Path::new(&dir).join(year.to_string()));
+    }
+    dirs.extend(workspace_data_dir(year).ok());
+    dirs
+}
+
+/// The workspace of the year crates: the nearest directory holding `aoc-core`, looking up from
+/// the current directory and then from the running executable, such as `target/debug/aoc`. It is
+/// looked up at runtime so that a binary still finds it when run from elsewhere or moved.
+pub fn workspace_dir() -> Result<PathBuf> {
+    let starts = [env::current_dir().ok(), env::current_exe().ok()];
+    starts
+        .iter()
+        .flatten()
+        .flat_map(|start| start.ancestors())
+        .find(|dir| dir.join("aoc-core").join("Cargo.toml").is_file())
+        .map(Path::to_path_buf)
+        .ok_or_else(|| anyhow!("No workspace above the current directory or the executable"))
+}
+
+/// The `data` directory of a year crate in the workspace.
+pub fn workspace_data_dir(year: u32) -> Result<PathBuf> {
+    Ok(workspace_dir()?.join(format!("aoc-{}", year)).join("data"))
+}
+
+/// Paths tried by [`default_path`], one per data directory and lastly the download cache.
+pub fn candidates(year: u32, day: u32) -> Vec<PathBuf> {
+    let mut dirs = data_dirs(year);
+    dirs.extend(fetch::cache_dir().map(|dir| dir.join(year.to_string())));
+    dirs.iter().map(|dir| day_path(dir, day)).collect()
+}
+
+/// Resolves the input file of a day, the first of its [`candidates`] that exists.
+pub fn default_path(year: u32, day: u32) -> Result<PathBuf> {
+    let paths = candidates(year, day);
+    match paths.iter().find(|p| p.exists()) {
+        Some(path) => Ok(path.clone()),
+        None => bail!(
+            "No input for {} day {:02}, tried: {}",
+            year,
+            day,
+            paths
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Reads a day's input from its default location.
+pub fn read_input(year: u32, day: u32) -> Result<Input> {
+    InputSource::Default.read(year, day)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_input_source_from_str() {
+        assert_eq!("-".parse::<InputSource>().unwrap(), InputSource::Stdin);
+        assert_eq!(
+            "some/input.txt".parse::<InputSource>().unwrap(),
+            InputSource::Path(PathBuf::from("some/input.txt"))
+        );
+        assert!("".parse::<InputSource>().is_err());
+    }
+
+    #[test]
+    fn test_is_not_found() {
+        let err = InputSource::Path(PathBuf::from("no/such/input.txt"))
+            .read(2020, 1)
+            .unwrap_err();
+        assert!(is_not_found(&err));
+        let dir = env::temp_dir();
+        let err = InputSource::Path(dir).read(2020, 1).unwrap_err();
+        assert!(!is_not_found(&err), "{:#}", err);
+    }
+
+    #[test]
+    fn test_input_normalized() {
+        let input = Input::new("1 2 \r\n3\t\r\n\r\n");
+        assert_eq!(input.as_str(), "1 2\n3");
+        assert_eq!(input.lines().collect::<Vec<_>>(), vec!["1 2", "3"]);
+        assert_eq!(input.ints::<u32>().unwrap(), vec![1, 2, 3]);
+        assert_eq!(Input::new("\n\n").lines().count(), 0);
+    }
+
+    #[test]
+    fn test_param() {
+        const SUM: Param = Param::new("sum", "2020", "the sum");
+        let input = Input::new("1");
+        assert_eq!(input.param::<u32>(&SUM).unwrap(), 2020);
+        let input = input.with_params([("sum", "7")].into_iter().collect());
+        assert_eq!(input.param::<u32>(&SUM).unwrap(), 7);
+        let input = Input::new("1").with_params([("sum", "x")].into_iter().collect());
+        assert_eq!(
+            input.param::<u32>(&SUM).unwrap_err().to_string(),
+            "Invalid parameter sum=\"x\": invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn test_input_views() {
+        let input = Input::new("a:1 b:2\nc:3\n\n\n\nd:4\n");
+        assert_eq!(
+            input.blocks().collect::<Vec<_>>(),
+            vec!["a:1 b:2\nc:3", "d:4"]
+        );
+        assert_eq!(
+            input.tokens().collect::<Vec<_>>(),
+            vec!["a:1", "b:2", "c:3", "d:4"]
+        );
+
+        let err = Input::new("1\n2\nx3").ints::<i64>().unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+        assert_eq!(
+            err.message,
+            "invalid number \"x3\": invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn test_input_grid() {
+        let input = Input::new("..#\r\n#..\r\n");
+        assert_eq!(input.grid().unwrap(), vec![b"..#", b"#.."]);
+        let err = Input::new("..#\n#.").grid().unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.message, "expected rows of 3 cells");
+        let err = Input::new("..#\n\n#..").grid().unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+
+    #[test]
+    fn test_workspace_candidate() {
+        let paths = candidates(2020, 3);
+        let workspace = &paths[data_dirs(2020).len() - 1];
+        assert!(workspace.ends_with("aoc-2020/data/day_03/input.txt"));
+    }
+
+    #[test]
+    fn test_workspace_dir() {
+        // Tests run from the directory of their crate, inside the workspace.
+        let dir = workspace_dir().unwrap();
+        assert!(dir.join("aoc-2020").join("Cargo.toml").is_file());
+        assert_eq!(
+            workspace_data_dir(2020).unwrap(),
+            dir.join("aoc-2020").join("data")
+        );
+    }
+
+    #[test]
+    fn test_missing_input() {
+        let err = default_path(1999, 1).unwrap_err().to_string();
+        assert!(err.starts_with("No input for 1999 day 01"));
+    }
+}
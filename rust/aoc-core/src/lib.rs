@@ -0,0 +1,27 @@
+// Lets code generated by `aoc-derive` name this crate from within it.
+extern crate self as aoc_core;
+
+mod answer;
+pub mod answers;
+pub mod bench;
+pub mod check;
+pub mod examples;
+pub mod fetch;
+pub mod input;
+pub mod limits;
+pub mod params;
+pub mod parse;
+pub mod pattern;
+pub mod records;
+pub mod report;
+pub mod runner;
+pub mod submit;
+mod solution;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
+pub use answer::Answer;
+pub use input::{read_input, Input, InputSource};
+pub use params::{Param, Params};
+pub use parse::{ParseError, ParseErrorKind};
+pub use solution::{solution, DynSolution, Part, Solution};
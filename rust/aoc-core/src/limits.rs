@@ -0,0 +1,473 @@
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::PathBuf;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+
+use crate::runner::{self, RunResult, Status};
+use crate::{DynSolution, Input, Params, Part};
+
+/// Message Rust's default allocation error handler prints before aborting.
+const ALLOCATION_FAILED: &str = "memory allocation of";
+
+/// Whether a process was killed by `SIGABRT`, as the default allocation error handler aborts even
+/// when it could not print its message.
+#[cfg(unix)]
+fn aborted(status: &ExitStatus) -> bool {
+    use std::os::unix::process::ExitStatusExt;
+    status.signal() == Some(libc::SIGABRT)
+}
+
+#[cfg(not(unix))]
+fn aborted(_status: &ExitStatus) -> bool {
+    false
+}
+
+/// Limits a part must run within.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// Wall-clock time. The first part run also pays for parsing the input.
+    pub time: Option<Duration>,
+    /// Address space of the process running the part, in bytes.
+    pub memory: Option<u64>,
+}
+
+impl Limits {
+    pub fn is_none(&self) -> bool {
+        self.time.is_none() && self.memory.is_none()
+    }
+}
+
+/// Parses a duration such as `500ms`, `10s`, `1.5s` or `2m`.
+pub fn parse_duration(s: &str) -> Result<Duration> {
+    let s = s.trim();
+    let (number, unit) = s.split_at(s.find(|c: char| c.is_alphabetic()).unwrap_or(s.len()));
+    let scale = match unit {
+        "ms" => 0.001,
+        "s" | "" => 1.0,
+        "m" => 60.0,
+        _ => bail!("Invalid duration {:?}, expected e.g. 500ms, 10s or 2m", s),
+    };
+    let number = number
+        .parse::<f64>()
+        .with_context(|| format!("Invalid duration {:?}", s))?;
+    if !number.is_finite() || number <= 0.0 {
+        bail!("Invalid duration {:?}, expected a positive amount", s);
+    }
+    Ok(Duration::from_secs_f64(number * scale))
+}
+
+/// Parses a size in bytes such as `1048576`, `512K`, `256M` or `2G`, in multiples of 1024.
+pub fn parse_size(s: &str) -> Result<u64> {
+    let s = s.trim();
+    let (number, unit) = s.split_at(s.find(|c: char| c.is_alphabetic()).unwrap_or(s.len()));
+    let scale = match unit.to_ascii_uppercase().trim_end_matches('B') {
+        "" => 1,
+        "K" => 1 << 10,
+        "M" => 1 << 20,
+        "G" => 1 << 30,
+        _ => bail!("Invalid size {:?}, expected e.g. 512K, 256M or 2G", s),
+    };
+    let number = number
+        .parse::<u64>()
+        .with_context(|| format!("Invalid size {:?}", s))?;
+    number
+        .checked_mul(scale)
+        .filter(|&n| n > 0)
+        .with_context(|| format!("Invalid size {:?}", s))
+}
+
+/// Restricts the address space of this process, so allocating past `bytes` fails.
+#[cfg(unix)]
+pub fn set_memory_limit(bytes: u64) -> Result<()> {
+    let bytes = bytes as libc::rlim_t;
+    let limit = libc::rlimit {
+        rlim_cur: bytes,
+        rlim_max: bytes,
+    };
+    // SAFETY: setrlimit only reads the struct it is given.
+    if unsafe { libc::setrlimit(libc::RLIMIT_AS, &limit) } != 0 {
+        bail!(
+            "Failed to set the memory limit: {}",
+            io::Error::last_os_error()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn set_memory_limit(_bytes: u64) -> Result<()> {
+    bail!("Memory limits are only supported on Unix")
+}
+
+/// The body of a worker process: runs `parts` on the input read from stdin with `params`,
+/// writing one JSON result per line to stdout as each part is done.
+pub fn serve_worker(
+    solution: &dyn DynSolution,
+    parts: &[Part],
+    params: Params,
+    memory: Option<u64>,
+) -> Result<()> {
+    let mut input = String::new();
+    io::stdin()
+        .read_to_string(&mut input)
+        .context("Failed to read input from stdin")?;
+    if let Some(memory) = memory {
+        set_memory_limit(memory)?;
+    }
+    let input = Input::new(&input).with_params(params);
+    let mut stdout = io::stdout().lock();
+    let mut written = Ok(());
+    runner::run_each(solution, &input, parts, |result| {
+        if written.is_ok() {
+            written = serde_json::to_writer(&mut stdout, &result)
+                .map_err(io::Error::from)
+                .and_then(|_| writeln!(stdout))
+                .and_then(|_| stdout.flush());
+        }
+    });
+    written.context("Failed to write a result")
+}
+
+/// Where a batch of parts runs: a thread, or a child process with its stderr passed through and
+/// collected.
+struct Worker {
+    results: Receiver<RunResult>,
+    process: Option<(Child, JoinHandle<String>)>,
+}
+
+/// Why a worker stopped before sending all its results.
+enum Stop {
+    Timeout,
+    Exited,
+}
+
+/// Runs parts under [`Limits`]: in a worker thread when only time is limited, and in a worker
+/// process when memory is. A part past a limit is abandoned, and the parts after it are run by a
+/// fresh worker.
+///
+/// A thread cannot be stopped, so a part past the time limit in a worker thread keeps running in
+/// the background and competes for CPU with the parts after it, slowing them down and skewing
+/// their timings. For strict limits, also limit memory: a worker process past the time limit is
+/// killed.
+#[derive(Clone, Debug, Default)]
+pub struct Limited {
+    limits: Limits,
+    worker: Option<(PathBuf, Vec<String>)>,
+}
+
+impl Limited {
+    pub fn new(limits: Limits) -> Self {
+        Self {
+            limits,
+            worker: None,
+        }
+    }
+
+    /// Sets the command starting a worker process. It is given the year, the day and the parts
+    /// to run as extra arguments, followed by `--memory-limit <bytes>` and a `--param
+    /// <name>=<value>` for each parameter of the input, and must call [`serve_worker`].
+    pub fn with_worker(self, program: impl Into<PathBuf>, args: &[&str]) -> Self {
+        Self {
+            worker: Some((
+                program.into(),
+                args.iter().map(|a| a.to_string()).collect(),
+            )),
+            ..self
+        }
+    }
+
+    pub fn run(&self, solution: &dyn DynSolution, input: &Input, parts: &[Part]) -> Vec<RunResult> {
+        if self.limits.is_none() {
+            return runner::run(solution, input, parts);
+        }
+        let mut results = vec![];
+        let mut remaining = parts;
+        while let Some(&part) = remaining.first() {
+            let mut worker = match self.spawn(solution, input, remaining) {
+                Ok(worker) => worker,
+                Err(e) => {
+                    let e = format!("{:#}", e);
+                    results.extend(
+                        remaining
+                            .iter()
+                            .map(|&part| RunResult::error(solution, part, &e)),
+                    );
+                    break;
+                }
+            };
+            let (done, stop) = self.collect(&worker, remaining.len());
+            let part = remaining.get(done.len()).copied().unwrap_or(part);
+            remaining = &remaining[(done.len() + 1).min(remaining.len())..];
+            results.extend(done);
+            if let Some(stop) = stop {
+                results.push(self.stopped(solution, part, stop, &mut worker));
+            }
+        }
+        results
+    }
+
+    fn spawn(&self, solution: &dyn DynSolution, input: &Input, parts: &[Part]) -> Result<Worker> {
+        let (tx, rx) = mpsc::channel();
+        if self.limits.memory.is_none() {
+            let (solution, input, parts) = (solution.clone_box(), input.clone(), parts.to_vec());
+            thread::spawn(move || {
+                runner::run_each(solution.as_ref(), &input, &parts, |result| {
+                    let _ = tx.send(result);
+                })
+            });
+            return Ok(Worker {
+                results: rx,
+                process: None,
+            });
+        }
+
+        let Some((program, args)) = &self.worker else {
+            bail!("A memory limit needs a worker process");
+        };
+        let mut child = Command::new(program)
+            .args(args)
+            .arg(solution.year().to_string())
+            .arg(solution.day().to_string())
+            .args(parts.iter().map(Part::to_string))
+            .arg("--memory-limit")
+            .arg(self.limits.memory.unwrap_or_default().to_string())
+            .args(
+                input
+                    .params()
+                    .iter()
+                    .flat_map(|(name, value)| ["--param".to_string(), format!("{}={}", name, value)]),
+            )
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Failed to start a worker with {}", program.display()))?;
+
+        let mut stdin = child.stdin.take().unwrap();
+        let input = input.to_string();
+        thread::spawn(move || stdin.write_all(input.as_bytes()));
+        let stdout = child.stdout.take().unwrap();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                let sent = serde_json::from_str(&line)
+                    .ok()
+                    .is_some_and(|result| tx.send(result).is_ok());
+                if !sent {
+                    break;
+                }
+            }
+        });
+        let stderr = BufReader::new(child.stderr.take().unwrap());
+        let errors = thread::spawn(move || {
+            // Passed through as it comes, since it also holds the logs of the worker.
+            let mut errors = String::new();
+            for line in stderr.lines().map_while(Result::ok) {
+                eprintln!("{}", line);
+                errors.push_str(&line);
+                errors.push('\n');
+            }
+            errors
+        });
+        Ok(Worker {
+            results: rx,
+            process: Some((child, errors)),
+        })
+    }
+
+    /// Waits for up to `n` results, each within the time limit.
+    fn collect(&self, worker: &Worker, n: usize) -> (Vec<RunResult>, Option<Stop>) {
+        let mut results = vec![];
+        while results.len() < n {
+            let result = match self.limits.time {
+                Some(time) => worker.results.recv_timeout(time),
+                None => worker
+                    .results
+                    .recv()
+                    .map_err(|_| RecvTimeoutError::Disconnected),
+            };
+            match result {
+                Ok(result) => results.push(result),
+                Err(RecvTimeoutError::Timeout) => return (results, Some(Stop::Timeout)),
+                Err(RecvTimeoutError::Disconnected) => return (results, Some(Stop::Exited)),
+            }
+        }
+        (results, None)
+    }
+
+    /// The result of a part whose worker stopped while running it.
+    fn stopped(
+        &self,
+        solution: &dyn DynSolution,
+        part: Part,
+        stop: Stop,
+        worker: &mut Worker,
+    ) -> RunResult {
+        let exited = worker.process.take().map(|(mut child, errors)| {
+            if let Stop::Timeout = stop {
+                let _ = child.kill();
+            }
+            let status = child.wait();
+            (status, errors.join().unwrap_or_default())
+        });
+        let out_of_memory = |status: &io::Result<ExitStatus>, errors: &str| {
+            errors.contains(ALLOCATION_FAILED)
+                || (self.limits.memory.is_some() && status.as_ref().is_ok_and(aborted))
+        };
+        // How the worker exited, such as ` (signal: 6 (SIGABRT))`.
+        let exit = |status: &io::Result<ExitStatus>| {
+            status
+                .as_ref()
+                .map_or(String::new(), |status| format!(" ({})", status))
+        };
+        let (status, message) = match (stop, exited) {
+            (Stop::Timeout, _) => (
+                Status::Timeout,
+                format!("ran past the time limit of {:?}", self.limits.time.unwrap_or_default()),
+            ),
+            (Stop::Exited, Some((status, errors))) if out_of_memory(&status, &errors) => (
+                Status::MemoryExceeded,
+                format!(
+                    "ran past the memory limit of {} bytes{}",
+                    self.limits.memory.unwrap_or_default(),
+                    exit(&status)
+                ),
+            ),
+            (Stop::Exited, Some((status, errors))) => (
+                Status::Error,
+                match (status, errors.lines().last()) {
+                    (status, Some(line)) => format!("worker failed: {}{}", line, exit(&status)),
+                    (Ok(status), None) => format!("worker failed: {}", status),
+                    (Err(e), None) => format!("worker failed: {}", e),
+                },
+            ),
+            (Stop::Exited, None) => (Status::Error, "worker stopped".to_string()),
+        };
+        RunResult {
+            error: Some(message),
+            duration: match status {
+                Status::Timeout => self.limits.time.unwrap_or_default(),
+                _ => Duration::ZERO,
+            },
+            ..RunResult::new(solution, part, status)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::thread;
+
+    use super::*;
+    use crate::testing::statuses;
+    use crate::{solution, Answer, Solution};
+
+    struct Sleepy;
+
+    impl Solution for Sleepy {
+        const YEAR: u32 = 2000;
+        const DAY: u32 = 3;
+
+        type Model = u64;
+        type Answer = u64;
+
+        fn parse(input: &Input) -> anyhow::Result<u64> {
+            Ok(input.trim().parse()?)
+        }
+
+        fn part1(model: &u64) -> anyhow::Result<u64> {
+            thread::sleep(Duration::from_millis(*model));
+            Ok(*model)
+        }
+
+        fn part2(model: &u64) -> anyhow::Result<u64> {
+            Ok(model + 1)
+        }
+    }
+
+    #[test]
+    fn test_parse_limits() {
+        assert_eq!(parse_duration("500ms").unwrap(), Duration::from_millis(500));
+        assert_eq!(parse_duration("1.5s").unwrap(), Duration::from_millis(1500));
+        assert_eq!(parse_duration("2m").unwrap(), Duration::from_secs(120));
+        assert_eq!(parse_duration("3").unwrap(), Duration::from_secs(3));
+        assert!(parse_duration("0s").is_err());
+        assert!(parse_duration("5h").is_err());
+
+        assert_eq!(parse_size("1024").unwrap(), 1024);
+        assert_eq!(parse_size("512K").unwrap(), 512 << 10);
+        assert_eq!(parse_size("256mb").unwrap(), 256 << 20);
+        assert_eq!(parse_size("2G").unwrap(), 2 << 30);
+        assert!(parse_size("2T").is_err());
+        assert!(parse_size("-1M").is_err());
+    }
+
+    #[test]
+    fn test_time_limit() {
+        let s = solution::<Sleepy>();
+        let limited = Limited::new(Limits {
+            time: Some(Duration::from_millis(200)),
+            memory: None,
+        });
+        let results = limited.run(s.as_ref(), &Input::new("10"), &Part::ALL);
+        assert_eq!(statuses(&results), vec![Status::Ok, Status::Ok]);
+
+        let results = limited.run(s.as_ref(), &Input::new("5000"), &Part::ALL);
+        assert_eq!(statuses(&results), vec![Status::Timeout, Status::Ok]);
+        assert_eq!(results[0].part, Part::One);
+        assert_eq!(results[1].answer, Some(Answer::UInt(5001)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_worker_process() {
+        let s = solution::<Sleepy>();
+        let limits = Limits {
+            time: Some(Duration::from_millis(500)),
+            memory: Some(1 << 30),
+        };
+        assert!(Limited::new(limits)
+            .run(s.as_ref(), &Input::new("1"), &Part::ALL)
+            .iter()
+            .all(|r| r.status == Status::Error));
+
+        // A stand-in worker: answers part 1 and hangs on part 2.
+        let line = r#"{"year":2000,"day":3,"part":1,"answer":"7","status":"ok","error":null,"duration_ms":1.5}"#;
+        let script = format!("cat >/dev/null; echo '{}'; sleep 5", line);
+        let results = Limited::new(limits)
+            .with_worker("sh", &["-c", &script, "worker"])
+            .run(s.as_ref(), &Input::new("1"), &Part::ALL);
+        assert_eq!(statuses(&results), vec![Status::Ok, Status::Timeout]);
+        assert_eq!(results[0].answer, Some(Answer::UInt(7)));
+        assert_eq!(results[0].duration, Duration::from_micros(1500));
+
+        let script = format!("echo '{} bytes failed' >&2; kill -ABRT $$", ALLOCATION_FAILED);
+        let results = Limited::new(limits)
+            .with_worker("sh", &["-c", &script, "worker"])
+            .run(s.as_ref(), &Input::new("1"), &Part::ALL);
+        assert_eq!(
+            statuses(&results),
+            vec![Status::MemoryExceeded, Status::MemoryExceeded]
+        );
+
+        // An abort without the message, as when the handler itself cannot allocate.
+        let results = Limited::new(limits)
+            .with_worker("sh", &["-c", "kill -ABRT $$", "worker"])
+            .run(s.as_ref(), &Input::new("1"), &[Part::One]);
+        assert_eq!(statuses(&results), vec![Status::MemoryExceeded]);
+        assert!(results[0].error.as_ref().unwrap().contains("SIGABRT"));
+
+        let script = "echo 'worker: unknown day' >&2; exit 2";
+        let results = Limited::new(limits)
+            .with_worker("sh", &["-c", script, "worker"])
+            .run(s.as_ref(), &Input::new("1"), &[Part::Two]);
+        assert_eq!(statuses(&results), vec![Status::Error]);
+        assert_eq!(
+            results[0].error.as_deref(),
+            Some("worker failed: worker: unknown day (exit status: 2)")
+        );
+    }
+}
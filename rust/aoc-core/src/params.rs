@@ -0,0 +1,195 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::answers::parse_day_key;
+
+/// A named puzzle parameter a day declares, such as the sum the entries of 2020 day 1 must add
+/// up to, with the value the puzzle uses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: &'static str,
+    pub help: &'static str,
+}
+
+impl Param {
+    pub const fn new(name: &'static str, default: &'static str, help: &'static str) -> Self {
+        Self {
+            name,
+            default,
+            help,
+        }
+    }
+}
+
+/// Parameter values by name, kept as written and parsed by the day reading them.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Params(BTreeMap<String, String>);
+
+impl Params {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+
+    pub fn set(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.0.insert(name.into(), value.into());
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Sets every value of `other`, replacing those already set.
+    pub fn extend(&mut self, other: &Params) {
+        self.0.extend(other.0.clone());
+    }
+
+    /// The value of each of `declared`, given or default. Values that are not declared are an
+    /// error.
+    pub fn resolve(&self, declared: &[Param]) -> Result<Params> {
+        if let Some(name) = self.0.keys().find(|k| !declared.iter().any(|p| p.name == *k)) {
+            let names: Vec<_> = declared.iter().map(|p| p.name).collect();
+            match names.as_slice() {
+                [] => bail!("Unknown parameter {:?}, the day takes none", name),
+                _ => bail!(
+                    "Unknown parameter {:?}, expected one of {}",
+                    name,
+                    names.join(", ")
+                ),
+            }
+        }
+        Ok(Params(
+            declared
+                .iter()
+                .map(|p| {
+                    let value = self.get(p.name).unwrap_or(p.default);
+                    (p.name.to_string(), value.to_string())
+                })
+                .collect(),
+        ))
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for Params {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Params(
+            iter.into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        )
+    }
+}
+
+/// Parses a `name=value` assignment, as given on the command line.
+pub fn parse_assignment(s: &str) -> Result<(String, String)> {
+    match s.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_string(), value.trim().to_string()))
+        }
+        _ => bail!("Invalid parameter {:?}, expected name=value", s),
+    }
+}
+
+/// Loads parameter values for the days of a year, from a file with one table per day:
+///
+/// ```toml
+/// [day_01]
+/// target_sum = 3000
+/// ```
+pub fn load(path: &Path) -> Result<BTreeMap<u32, Params>> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    parse_file(&content).with_context(|| format!("Invalid parameters file {}", path.display()))
+}
+
+fn parse_file(content: &str) -> Result<BTreeMap<u32, Params>> {
+    let tables: BTreeMap<String, BTreeMap<String, toml::Value>> = toml::from_str(content)?;
+    tables
+        .into_iter()
+        .map(|(key, values)| {
+            let params = values
+                .into_iter()
+                .map(|(name, value)| match value {
+                    toml::Value::String(s) => Ok((name, s)),
+                    toml::Value::Integer(_) | toml::Value::Float(_) | toml::Value::Boolean(_) => {
+                        Ok((name, value.to_string()))
+                    }
+                    _ => bail!("Invalid value for {:?}, expected a string or a number", name),
+                })
+                .collect::<Result<_>>()?;
+            Ok((parse_day_key(&key)?, params))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const DECLARED: &[Param] = &[
+        Param::new("target", "2020", "the sum"),
+        Param::new("tree", "#", "the tree cell"),
+    ];
+
+    #[test]
+    fn test_resolve() {
+        let params: Params = [("target", "3000")].into_iter().collect();
+        let resolved = params.resolve(DECLARED).unwrap();
+        assert_eq!(
+            resolved.iter().collect::<Vec<_>>(),
+            vec![("target", "3000"), ("tree", "#")]
+        );
+        assert_eq!(Params::new().resolve(&[]).unwrap(), Params::new());
+
+        let params: Params = [("targte", "3000")].into_iter().collect();
+        let err = params.resolve(DECLARED).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unknown parameter \"targte\", expected one of target, tree"
+        );
+        assert!(params.resolve(&[]).is_err());
+    }
+
+    #[test]
+    fn test_parse_assignment() {
+        assert_eq!(
+            parse_assignment("target_sum=3000").unwrap(),
+            ("target_sum".to_string(), "3000".to_string())
+        );
+        assert_eq!(parse_assignment("slopes=1,1 3,1").unwrap().1, "1,1 3,1");
+        assert_eq!(parse_assignment("empty=").unwrap().1, "");
+        assert!(parse_assignment("target_sum").is_err());
+        assert!(parse_assignment("=3").is_err());
+    }
+
+    #[test]
+    fn test_parse_file() {
+        let days = parse_file(
+            r#"
+            [day_01]
+            target_sum = 3000
+
+            [day_03]
+            tree = "@"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(days[&1].get("target_sum"), Some("3000"));
+        assert_eq!(days[&3].get("tree"), Some("@"));
+        assert!(parse_file("[day_01]\nslopes = [1, 2]").is_err());
+        assert!(parse_file("[one]\nx = 1").is_err());
+    }
+}
@@ -0,0 +1,258 @@
+use std::error;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use nom::error::ErrorKind;
+
+/// An error in a puzzle input, located by line and column so it can be shown with the offending
+/// line and a caret under the problem:
+///
+/// ```text
+/// line 2, column 9: unexpected "x", expected a number
+///   |
+/// 2 | Game 2: x blue
+///   |         ^
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, in characters.
+    pub column: usize,
+    /// The whole offending line.
+    pub text: String,
+    pub message: String,
+    pub kind: ParseErrorKind,
+}
+
+/// What a [`ParseError`] is about, for callers that skip some errors rather than fail.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// Malformed input or an invalid value.
+    #[default]
+    Invalid,
+    /// A required field of a record is missing.
+    MissingField,
+}
+
+impl ParseError {
+    /// An error at byte `offset` of `input`.
+    pub fn at(input: &str, offset: usize, message: impl Display) -> Self {
+        let mut offset = offset.min(input.len());
+        while !input.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+        Self {
+            line: input[..start].matches('\n').count() + 1,
+            column: input[start..offset].chars().count() + 1,
+            text: input[start..end].trim_end_matches('\r').to_string(),
+            message: message.to_string(),
+            kind: ParseErrorKind::Invalid,
+        }
+    }
+
+    pub fn with_kind(self, kind: ParseErrorKind) -> Self {
+        Self { kind, ..self }
+    }
+
+    /// An error at `at`, which must be a slice of `input`, as the remaining input of a parser is.
+    pub fn at_str(input: &str, at: &str, message: impl Display) -> Self {
+        let offset = (at.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+        debug_assert!(
+            offset <= input.len(),
+            "{:?} is not a slice of the input",
+            at
+        );
+        Self::at(input, offset, message)
+    }
+
+    /// Converts the error of a nom parser that ran on `input`, or on a slice of it.
+    pub fn from_nom(input: &str, err: nom::Err<nom::error::Error<&str>>) -> Self {
+        match err {
+            nom::Err::Incomplete(_) => Self::at(input, input.len(), "unexpected end of input"),
+            nom::Err::Error(e) | nom::Err::Failure(e) => {
+                let at = skip_blanks(e.input);
+                let found = match at.split_whitespace().next() {
+                    Some(token) => format!("unexpected {:?}", token),
+                    None => "unexpected end of line".to_string(),
+                };
+                Self::at_str(
+                    input,
+                    at,
+                    format!("{}, expected {}", found, expected(e.code)),
+                )
+            }
+        }
+    }
+
+    /// Runs a nom parser to completion: its error is converted and any input left over after it
+    /// is an error.
+    pub fn finish<'a, O>(
+        input: &'a str,
+        result: nom::IResult<&'a str, O>,
+    ) -> Result<O, ParseError> {
+        match result {
+            Ok((rest, _)) if !rest.trim().is_empty() => Err(Self::at_str(
+                input,
+                skip_blanks(rest),
+                "unexpected trailing input",
+            )),
+            Ok((_, output)) => Ok(output),
+            Err(e) => Err(Self::from_nom(input, e)),
+        }
+    }
+
+    /// Moves an error found in a piece of a larger input down by the lines preceding the piece.
+    pub fn shift(self, lines: usize) -> Self {
+        Self {
+            line: self.line + lines,
+            ..self
+        }
+    }
+}
+
+/// Skips spaces and tabs, so errors point at the offending token rather than the blank before it.
+fn skip_blanks(s: &str) -> &str {
+    s.trim_start_matches([' ', '\t'])
+}
+
+fn expected(kind: ErrorKind) -> &'static str {
+    match kind {
+        ErrorKind::Tag => "a keyword",
+        ErrorKind::Char => "a separator",
+        ErrorKind::Digit => "a number",
+        ErrorKind::Alpha | ErrorKind::AlphaNumeric => "a word",
+        ErrorKind::Space | ErrorKind::MultiSpace => "whitespace",
+        ErrorKind::CrLf => "a line break",
+        ErrorKind::Eof => "the end of the input",
+        ErrorKind::OneOf | ErrorKind::Alt => "one of the allowed values",
+        _ => "something else",
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        writeln!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", number, self.text)?;
+        write!(f, "{} | {}^", gutter, " ".repeat(self.column - 1))
+    }
+}
+
+impl error::Error for ParseError {}
+
+/// Parses each line of `input` with `f`, whose errors are located within the line. Like
+/// [`Input::lines`](crate::Input::lines), an empty input has no lines.
+pub fn parse_lines<'a, T>(
+    input: &'a str,
+    mut f: impl FnMut(&'a str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| f(line).map_err(|e| e.shift(i)))
+        .collect()
+}
+
+/// Parses each line of `input` as a `T`, e.g. a list of numbers.
+pub fn parse_values<T>(input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    parse_lines(input, |line| {
+        line.trim()
+            .parse()
+            .map_err(|e| ParseError::at(line, 0, format!("invalid value {:?}: {}", line, e)))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use nom::character::complete::{char, u32};
+    use nom::sequence::separated_pair;
+
+    use super::*;
+    use crate::Input;
+
+    const INPUT: &str = "1-3\n2-4\n3x5\n";
+
+    fn range(line: &str) -> Result<(u32, u32), ParseError> {
+        ParseError::finish(line, separated_pair(u32, char('-'), u32)(line))
+    }
+
+    #[test]
+    fn test_at() {
+        let err = ParseError::at(INPUT, 9, "bad");
+        assert_eq!(
+            err,
+            ParseError {
+                line: 3,
+                column: 2,
+                text: "3x5".to_string(),
+                message: "bad".to_string(),
+                kind: ParseErrorKind::Invalid,
+            }
+        );
+        assert_eq!(ParseError::at(INPUT, 0, "bad").text, "1-3");
+        assert_eq!(ParseError::at("é-x", 3, "bad").column, 3);
+    }
+
+    #[test]
+    fn test_from_nom() {
+        let line = INPUT.split('\n').nth(2).unwrap();
+        let err = range(line).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 2));
+        assert_eq!(err.message, "unexpected \"x5\", expected a separator");
+
+        let err = range("1-").unwrap_err();
+        assert_eq!(
+            (err.column, err.message.as_str()),
+            (3, "unexpected end of line, expected a number")
+        );
+
+        let err = range("1-2 3").unwrap_err();
+        assert_eq!(
+            (err.column, err.message.as_str()),
+            (5, "unexpected trailing input")
+        );
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let err = parse_lines(INPUT.trim_end(), range).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 2));
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 2: unexpected \"x5\", expected a separator\n  |\n3 | 3x5\n  |  ^"
+        );
+        assert_eq!(
+            parse_lines("1-3\n2-4", range).unwrap(),
+            vec![(1, 3), (2, 4)]
+        );
+        assert_eq!(parse_lines(INPUT, range).unwrap_err().line, 3);
+        assert!(parse_lines("", range).unwrap().is_empty());
+        assert!(parse_values::<u32>(&Input::new("")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_values() {
+        assert_eq!(parse_values::<i64>("1\n-2\n3").unwrap(), vec![1, -2, 3]);
+        let err = parse_values::<u64>("1\n2\nthree").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+        assert_eq!(
+            err.message,
+            "invalid value \"three\": invalid digit found in string"
+        );
+    }
+}
@@ -0,0 +1,207 @@
+//! Line patterns such as `{first}-{second} {character}: {password}`, the runtime side of
+//! `#[derive(AocParse)]`.
+//!
+//! A pattern is literal text with `{field}` placeholders. A placeholder takes everything up to
+//! the first occurrence of the literal following it, or up to its last occurrence when that
+//! literal ends the pattern, so `({tag})` reads `x)` from `(x))`. A placeholder that comes last
+//! takes the rest of the line. The text is converted to the field's type with `FromStr`.
+//!
+//! ```
+//! use aoc_core::pattern::AocParse;
+//!
+//! #[derive(AocParse, Debug, PartialEq)]
+//! #[aoc(pattern = "{x},{y}")]
+//! struct Point {
+//!     x: i32,
+//!     y: i32,
+//! }
+//!
+//! assert_eq!("3,-4".parse(), Ok(Point { x: 3, y: -4 }));
+//! let err = "3,four".parse::<Point>().unwrap_err();
+//! assert_eq!(err.column, 3);
+//! ```
+//!
+//! Each field has exactly one placeholder, checked at compile time:
+//!
+//! ```compile_fail
+//! # use aoc_core::pattern::AocParse;
+//! #[derive(AocParse)]
+//! #[aoc(pattern = "{x},{x}")]
+//! struct Point {
+//!     x: i32,
+//! }
+//! ```
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+pub use aoc_derive::AocParse;
+
+use crate::ParseError;
+
+/// A piece of a pattern, as split by the derive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Piece {
+    Literal(&'static str),
+    Field(&'static str),
+}
+
+/// A type read from a line matching a pattern. Derive it rather than implementing it: the derive
+/// also implements `FromStr` in terms of it.
+///
+/// A field marked `#[aoc(flatten)]` is a nested `AocParse` type: the text of its placeholder is
+/// matched against the pattern of its own type.
+pub trait AocParse: Sized {
+    const PATTERN: &'static [Piece];
+
+    fn from_fields(fields: &Fields) -> Result<Self, ParseError>;
+}
+
+/// The text of each placeholder of a pattern, matched against a line.
+#[derive(Debug)]
+pub struct Fields<'a> {
+    line: &'a str,
+    values: Vec<(&'static str, &'a str)>,
+}
+
+impl<'a> Fields<'a> {
+    /// Matches `line` against `pattern`.
+    pub fn matching(line: &'a str, pattern: &[Piece]) -> Result<Self, ParseError> {
+        Self::matching_slice(line, line, pattern)
+    }
+
+    /// Matches `text`, a slice of `line`, against `pattern`, locating errors in `line`.
+    fn matching_slice(line: &'a str, text: &'a str, pattern: &[Piece]) -> Result<Self, ParseError> {
+        let mut values = vec![];
+        let mut rest = text;
+        for (i, piece) in pattern.iter().enumerate() {
+            match *piece {
+                Piece::Literal(literal) => {
+                    rest = rest.strip_prefix(literal).ok_or_else(|| {
+                        ParseError::at_str(line, rest, format!("expected {:?}", literal))
+                    })?;
+                }
+                Piece::Field(name) => {
+                    let end = match pattern.get(i + 1) {
+                        Some(Piece::Literal(next)) => if i + 2 == pattern.len() {
+                            rest.rfind(next)
+                        } else {
+                            rest.find(next)
+                        }
+                        .ok_or_else(|| {
+                            ParseError::at_str(line, rest, format!("expected {:?}", next))
+                        })?,
+                        _ => rest.len(),
+                    };
+                    values.push((name, &rest[..end]));
+                    rest = &rest[end..];
+                }
+            }
+        }
+        if !rest.is_empty() {
+            return Err(ParseError::at_str(line, rest, "unexpected trailing input"));
+        }
+        Ok(Self { line, values })
+    }
+
+    /// The text of placeholder `name`.
+    fn value(&self, name: &str) -> Result<&'a str, ParseError> {
+        self.values
+            .iter()
+            .find(|(field, _)| *field == name)
+            .map(|&(_, value)| value)
+            .ok_or_else(|| ParseError::at(self.line, 0, format!("no {{{}}} in the pattern", name)))
+    }
+
+    /// Converts the text of placeholder `name`, locating any error at it.
+    pub fn get<T>(&self, name: &str) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let value = self.value(name)?;
+        value
+            .parse()
+            .map_err(|e| ParseError::at_str(self.line, value, format!("invalid {}: {}", name, e)))
+    }
+
+    /// Reads the text of placeholder `name` as a nested `T`, locating any error in the line.
+    pub fn nested<T: AocParse>(&self, name: &str) -> Result<T, ParseError> {
+        let value = self.value(name)?;
+        T::from_fields(&Self::matching_slice(self.line, value, T::PATTERN)?)
+    }
+}
+
+/// Parses `line` as a `T`; what the derived `FromStr` calls.
+pub fn parse<T: AocParse>(line: &str) -> Result<T, ParseError> {
+    T::from_fields(&Fields::matching(line, T::PATTERN)?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(AocParse, Debug, PartialEq)]
+    #[aoc(pattern = "{low}..{high}")]
+    struct Range {
+        low: u32,
+        high: u32,
+    }
+
+    #[derive(AocParse, Debug, PartialEq)]
+    #[aoc(pattern = "{name} at {range} ({tag})")]
+    struct Named {
+        name: String,
+        #[aoc(flatten)]
+        range: Range,
+        tag: String,
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("1..20".parse(), Ok(Range { low: 1, high: 20 }));
+        assert_eq!(
+            "a b at 3..4 (x)".parse(),
+            Ok(Named {
+                name: "a b".to_string(),
+                range: Range { low: 3, high: 4 },
+                tag: "x".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_literal_in_value() {
+        // Only the last literal matches from the right.
+        let named = "a (b) at 3..4 (x) (y))".parse::<Named>().unwrap();
+        assert_eq!(
+            (named.name.as_str(), named.tag.as_str()),
+            ("a (b)", "x) (y)")
+        );
+        let err = "a at 3..4 x".parse::<Named>().unwrap_err();
+        assert_eq!((err.column, err.message.as_str()), (6, "expected \" (\""));
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = "1..x".parse::<Range>().unwrap_err();
+        assert_eq!(
+            (err.column, err.message.as_str()),
+            (4, "invalid high: invalid digit found in string")
+        );
+        let err = "1-2".parse::<Range>().unwrap_err();
+        assert_eq!((err.column, err.message.as_str()), (1, "expected \"..\""));
+        let err = "a at 1..x (xy)".parse::<Named>().unwrap_err();
+        assert_eq!(
+            (err.column, err.message.as_str()),
+            (9, "invalid high: invalid digit found in string")
+        );
+        let err = "a at 1-2 (xy)".parse::<Named>().unwrap_err();
+        assert_eq!((err.column, err.message.as_str()), (6, "expected \"..\""));
+        let err = "a at 1..2 (x)!".parse::<Named>().unwrap_err();
+        assert_eq!(
+            (err.column, err.message.as_str()),
+            (14, "unexpected trailing input")
+        );
+    }
+}
@@ -0,0 +1,302 @@
+//! A serde `Deserializer` for records of whitespace-separated `key:value` pairs, such as
+//!
+//! ```text
+//! ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+//! byr:1937 iyr:2017 cid:147 hgt:183cm
+//! ```
+//!
+//! read into any `#[derive(Deserialize)]` struct. A key ends at the first `:` of a pair, so values
+//! may contain colons. Values are converted to the field's type with `FromStr`, and an optional
+//! field is `None` when its key is missing. Add `#[serde(deny_unknown_fields)]` to reject keys the
+//! struct does not have.
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use serde::de::value::BorrowedStrDeserializer;
+use serde::de::{self, DeserializeSeed, Deserializer, MapAccess, Visitor};
+use serde::{forward_to_deserialize_any, Deserialize};
+
+use crate::{Input, ParseError, ParseErrorKind};
+
+/// Reads one record.
+pub fn from_str<'de, T: Deserialize<'de>>(record: &'de str) -> Result<T, ParseError> {
+    deserialize(record, record)
+}
+
+/// Reads the records of a batch, its [blocks](Input::blocks). Errors are located in the whole
+/// batch, and a missing field is a [`ParseErrorKind::MissingField`].
+pub fn from_batch<'de, T: Deserialize<'de>>(
+    batch: &'de Input,
+) -> impl Iterator<Item = Result<T, ParseError>> + 'de {
+    batch.blocks().map(move |record| deserialize(batch, record))
+}
+
+/// Reads `record`, a slice of `input`.
+fn deserialize<'de, T: Deserialize<'de>>(
+    input: &'de str,
+    record: &'de str,
+) -> Result<T, ParseError> {
+    T::deserialize(RecordDeserializer { record }).map_err(|e| {
+        // Errors raised once all pairs are read, such as a missing field, are about the record.
+        ParseError::at_str(input, e.at.unwrap_or(record), e.message).with_kind(e.kind)
+    })
+}
+
+#[derive(Debug)]
+struct Error<'de> {
+    message: String,
+    /// The pair, key or value the error is about.
+    at: Option<&'de str>,
+    kind: ParseErrorKind,
+}
+
+impl<'de> Error<'de> {
+    fn at(at: &'de str, message: impl Display) -> Self {
+        Self {
+            message: message.to_string(),
+            at: Some(at),
+            kind: ParseErrorKind::Invalid,
+        }
+    }
+
+    fn locate(mut self, at: &'de str) -> Self {
+        self.at.get_or_insert(at);
+        self
+    }
+}
+
+impl Display for Error<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for Error<'_> {}
+
+impl de::Error for Error<'_> {
+    fn custom<T: Display>(message: T) -> Self {
+        Self {
+            message: message.to_string(),
+            at: None,
+            kind: ParseErrorKind::Invalid,
+        }
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        Self {
+            kind: ParseErrorKind::MissingField,
+            ..Self::custom(format_args!("missing field `{}`", field))
+        }
+    }
+}
+
+struct RecordDeserializer<'de> {
+    record: &'de str,
+}
+
+impl<'de> Deserializer<'de> for RecordDeserializer<'de> {
+    type Error = Error<'de>;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_map(Pairs {
+            pairs: self.record.split_whitespace(),
+            value: "",
+        })
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct seq tuple tuple_struct map struct enum identifier
+        ignored_any
+    }
+}
+
+struct Pairs<'de, I> {
+    pairs: I,
+    /// The value of the last key read.
+    value: &'de str,
+}
+
+impl<'de, I: Iterator<Item = &'de str>> MapAccess<'de> for Pairs<'de, I> {
+    type Error = Error<'de>;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let Some(pair) = self.pairs.next() else {
+            return Ok(None);
+        };
+        let (key, value) = pair
+            .split_once(':')
+            .ok_or_else(|| Error::at(pair, "expected key:value"))?;
+        self.value = value;
+        seed.deserialize(BorrowedStrDeserializer::<Error>::new(key))
+            .map(Some)
+            .map_err(|e| e.locate(key))
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        seed.deserialize(ValueDeserializer(self.value))
+            .map_err(|e| e.locate(self.value))
+    }
+}
+
+struct ValueDeserializer<'de>(&'de str);
+
+impl<'de> ValueDeserializer<'de> {
+    fn parse<T>(&self) -> Result<T, Error<'de>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.0
+            .parse()
+            .map_err(|e| Error::at(self.0, format!("invalid value {:?}: {}", self.0, e)))
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident),* $(,)?) => {$(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            visitor.$visit(self.parse()?)
+        }
+    )*};
+}
+
+impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
+    type Error = Error<'de>;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_borrowed_str(self.0)
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_enum(BorrowedStrDeserializer::new(self.0))
+    }
+
+    forward_to_deserialize_any! {
+        str string bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier
+        ignored_any
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(rename_all = "lowercase")]
+    enum Unit {
+        Cm,
+        In,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(deny_unknown_fields)]
+    struct Record<'a> {
+        name: &'a str,
+        size: u32,
+        unit: Unit,
+        note: Option<String>,
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            from_str::<Record>("size:12 name:a:b\nunit:cm").unwrap(),
+            Record {
+                name: "a:b",
+                size: 12,
+                unit: Unit::Cm,
+                note: None,
+            }
+        );
+        let record: Record = from_str("note:hi name:x size:1 unit:in").unwrap();
+        assert_eq!(record.note.as_deref(), Some("hi"));
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = from_str::<Record>("name:a size:1 unit:cm colour:red").unwrap_err();
+        assert_eq!(err.column, 23);
+        assert!(
+            err.message.starts_with("unknown field `colour`"),
+            "{}",
+            err.message
+        );
+
+        let err = from_str::<Record>("name:a unit:cm").unwrap_err();
+        assert_eq!(
+            (err.column, err.message.as_str()),
+            (1, "missing field `size`")
+        );
+        assert_eq!(err.kind, ParseErrorKind::MissingField);
+
+        let err = from_str::<Record>("name:a size:big unit:cm").unwrap_err();
+        assert_eq!(
+            (err.column, err.message.as_str()),
+            (13, "invalid value \"big\": invalid digit found in string")
+        );
+        assert_eq!(err.kind, ParseErrorKind::Invalid);
+
+        let err = from_str::<Record>("name:a size unit:cm").unwrap_err();
+        assert_eq!(
+            (err.column, err.message.as_str()),
+            (8, "expected key:value")
+        );
+
+        let err = from_str::<Record>("name:a size:1 unit:mm").unwrap_err();
+        assert_eq!(err.column, 20);
+    }
+
+    #[test]
+    fn test_from_batch() {
+        let batch = Input::new(
+            "name:a size:1\r\nunit:cm\r\n\r\n \r\nname:b size:2 unit:in\n\nname:c\nsize:x unit:cm",
+        );
+        let records: Vec<Result<Record, _>> = from_batch(&batch).collect();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[1].as_ref().unwrap().name, "b");
+        let err = records[2].as_ref().unwrap_err();
+        assert_eq!((err.line, err.column), (8, 6));
+    }
+}
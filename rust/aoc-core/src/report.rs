@@ -0,0 +1,435 @@
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::io::{self, Write};
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{bail, Error, Result};
+
+use crate::runner::{RunResult, Status};
+use crate::Answer;
+
+/// Output format of run results.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// One human readable line per part.
+    #[default]
+    Text,
+    /// A single JSON array, written once every part has run.
+    Json,
+    /// One JSON object per line, written as soon as a part has run.
+    Ndjson,
+    /// A JUnit XML report with one test suite per year.
+    Junit,
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "ndjson" => Ok(Format::Ndjson),
+            "junit" => Ok(Format::Junit),
+            _ => bail!("Invalid format {:?}, expected text, json, ndjson or junit", s),
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Format::Text => write!(f, "text"),
+            Format::Json => write!(f, "json"),
+            Format::Ndjson => write!(f, "ndjson"),
+            Format::Junit => write!(f, "junit"),
+        }
+    }
+}
+
+/// Writes run results out in some format.
+pub trait Reporter {
+    fn report(&mut self, result: &RunResult) -> Result<()>;
+
+    /// Called once after the last result.
+    fn finish(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+pub fn reporter<'a>(format: Format, out: impl Write + 'a) -> Box<dyn Reporter + 'a> {
+    match format {
+        Format::Text => Box::new(TextReporter(out)),
+        Format::Json => Box::new(JsonReporter {
+            out,
+            results: vec![],
+        }),
+        Format::Ndjson => Box::new(NdjsonReporter(out)),
+        Format::Junit => Box::new(JunitReporter {
+            out,
+            results: vec![],
+        }),
+    }
+}
+
+struct TextReporter<W>(W);
+
+impl<W: Write> Reporter for TextReporter<W> {
+    fn report(&mut self, r: &RunResult) -> Result<()> {
+        let label = r.label();
+        let none = String::new();
+        let answer = r.answer.as_ref().map(Answer::to_string).unwrap_or_default();
+        let error = r.error.as_ref().unwrap_or(&none);
+        match r.status {
+            Status::Ok => writeln!(self.0, "{}: {}", label, answer)?,
+            Status::Pass => writeln!(self.0, "PASS    {}", label)?,
+            Status::Fail => writeln!(
+                self.0,
+                "FAIL    {}: expected {}, got {}",
+                label,
+                r.expected.as_ref().map(Answer::to_string).unwrap_or_default(),
+                answer
+            )?,
+            Status::Missing => writeln!(self.0, "MISSING {}: {}", label, error)?,
+            Status::Error => writeln!(self.0, "ERROR   {}: {}", label, error)?,
+            Status::Timeout => writeln!(self.0, "TIMEOUT {}: {}", label, error)?,
+            Status::MemoryExceeded => writeln!(self.0, "MEMORY  {}: {}", label, error)?,
+            Status::Panic => {
+                writeln!(self.0, "PANIC   {}: {}", label, error)?;
+                for line in r.backtrace.iter().flat_map(|b| b.lines()) {
+                    writeln!(self.0, "        {}", line)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+struct JsonReporter<W> {
+    out: W,
+    results: Vec<RunResult>,
+}
+
+impl<W: Write> Reporter for JsonReporter<W> {
+    fn report(&mut self, result: &RunResult) -> Result<()> {
+        self.results.push(result.clone());
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        let written = serde_json::to_writer_pretty(&mut self.out, &self.results)
+            .map_err(io::Error::from)
+            .and_then(|()| writeln!(self.out));
+        unless_closed(written)
+    }
+}
+
+/// Succeeds when the reader of the output has gone away, as `head` does after a few lines.
+fn unless_closed(written: io::Result<()>) -> Result<()> {
+    match written {
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        written => Ok(written?),
+    }
+}
+
+struct NdjsonReporter<W>(W);
+
+impl<W: Write> Reporter for NdjsonReporter<W> {
+    fn report(&mut self, result: &RunResult) -> Result<()> {
+        serde_json::to_writer(&mut self.0, result)?;
+        writeln!(self.0)?;
+        self.0.flush()?;
+        Ok(())
+    }
+}
+
+struct JunitReporter<W> {
+    out: W,
+    results: Vec<RunResult>,
+}
+
+/// Escapes text for XML. Control characters other than `\n` and `\t` cannot appear in XML 1.0, so
+/// they become U+FFFD.
+fn escape(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '\n' | '\t' => c.to_string(),
+            c if c < ' ' => char::REPLACEMENT_CHARACTER.to_string(),
+            '&' => "&amp;".to_string(),
+            '<' => "&lt;".to_string(),
+            '>' => "&gt;".to_string(),
+            '"' => "&quot;".to_string(),
+            '\'' => "&apos;".to_string(),
+            _ => c.to_string(),
+        })
+        .collect()
+}
+
+fn seconds(duration: Duration) -> String {
+    format!("{:.6}", duration.as_secs_f64())
+}
+
+fn write_testcase(out: &mut impl Write, r: &RunResult) -> io::Result<()> {
+    let name = match &r.example {
+        Some(example) => format!("{} part {}", example, r.part),
+        None => format!("part {}", r.part),
+    };
+    write!(
+        out,
+        "    <testcase classname=\"aoc-{}.day_{:02}\" name=\"{}\" time=\"{}\"",
+        r.year,
+        r.day,
+        escape(&name),
+        seconds(r.duration)
+    )?;
+    let error = escape(r.error.as_deref().unwrap_or_default());
+    let answer = escape(&r.answer.as_ref().map(Answer::to_string).unwrap_or_default());
+    match r.status {
+        Status::Ok | Status::Pass => {
+            writeln!(out, ">")?;
+            writeln!(out, "      <system-out>{}</system-out>", answer)?;
+        }
+        Status::Fail => {
+            writeln!(out, ">")?;
+            writeln!(
+                out,
+                "      <failure message=\"expected {}, got {}\"/>",
+                escape(&r.expected.as_ref().map(Answer::to_string).unwrap_or_default()),
+                answer
+            )?;
+        }
+        Status::Missing => {
+            writeln!(out, ">")?;
+            writeln!(out, "      <skipped message=\"{}\"/>", error)?;
+        }
+        Status::Error => {
+            writeln!(out, ">")?;
+            writeln!(out, "      <error message=\"{}\"/>", error)?;
+        }
+        Status::Timeout | Status::MemoryExceeded => {
+            writeln!(out, ">")?;
+            writeln!(
+                out,
+                "      <error message=\"{}\" type=\"{}\"/>",
+                error,
+                if r.status == Status::Timeout { "timeout" } else { "memory" }
+            )?;
+        }
+        Status::Panic => {
+            writeln!(out, ">")?;
+            writeln!(
+                out,
+                "      <error message=\"{}\" type=\"panic\">{}</error>",
+                error,
+                escape(r.backtrace.as_deref().unwrap_or_default())
+            )?;
+        }
+    }
+    writeln!(out, "    </testcase>")
+}
+
+impl<W: Write> Reporter for JunitReporter<W> {
+    fn report(&mut self, result: &RunResult) -> Result<()> {
+        self.results.push(result.clone());
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        let mut years = BTreeMap::<u32, Vec<&RunResult>>::new();
+        for r in &self.results {
+            years.entry(r.year).or_default().push(r);
+        }
+        let count = |results: &[&RunResult], status: Status| {
+            results.iter().filter(|r| r.status == status).count()
+        };
+        let errors = |results: &[&RunResult]| {
+            results
+                .iter()
+                .filter(|r| r.status.is_failure() && r.status != Status::Fail)
+                .count()
+        };
+        let all = self.results.iter().collect::<Vec<_>>();
+
+        let out = &mut self.out;
+        writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            out,
+            r#"<testsuites name="aoc" tests="{}" failures="{}" errors="{}">"#,
+            all.len(),
+            count(&all, Status::Fail),
+            errors(&all)
+        )?;
+        for (year, results) in years {
+            writeln!(
+                out,
+                r#"  <testsuite name="aoc-{}" tests="{}" failures="{}" errors="{}" skipped="{}" time="{}">"#,
+                year,
+                results.len(),
+                count(&results, Status::Fail),
+                errors(&results),
+                count(&results, Status::Missing),
+                seconds(results.iter().map(|r| r.duration).sum())
+            )?;
+            for r in results {
+                write_testcase(out, r)?;
+            }
+            writeln!(out, "  </testsuite>")?;
+        }
+        writeln!(out, "</testsuites>")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Params, Part};
+
+    fn result(part: Part, status: Status, answer: Option<&str>, error: Option<&str>) -> RunResult {
+        RunResult {
+            year: 2023,
+            day: 4,
+            part,
+            example: None,
+            answer: answer.map(Answer::from),
+            expected: None,
+            status,
+            error: error.map(str::to_string),
+            backtrace: None,
+            params: Params::new(),
+            duration: Duration::from_millis(2),
+        }
+    }
+
+    fn render(format: Format, results: &[RunResult]) -> String {
+        let mut out = vec![];
+        {
+            let mut reporter = reporter(format, &mut out);
+            for r in results {
+                reporter.report(r).unwrap();
+            }
+            reporter.finish().unwrap();
+        }
+        String::from_utf8(out).unwrap()
+    }
+
+    fn results() -> Vec<RunResult> {
+        vec![
+            result(Part::One, Status::Ok, Some("13"), None),
+            result(Part::Two, Status::Error, None, Some("bad <input>")),
+        ]
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        for format in [Format::Text, Format::Json, Format::Ndjson, Format::Junit] {
+            assert_eq!(format.to_string().parse::<Format>().unwrap(), format);
+        }
+        assert!("xml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!(
+            render(Format::Text, &results()),
+            "2023 day 04 part 1: 13\nERROR   2023 day 04 part 2: bad <input>\n"
+        );
+    }
+
+    #[test]
+    fn test_ndjson() {
+        let output = render(Format::Ndjson, &results());
+        let lines = output.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines[0],
+            r#"{"year":2023,"day":4,"part":1,"answer":"13","status":"ok","error":null,"duration_ms":2.0}"#
+        );
+        assert_eq!(
+            lines[1],
+            r#"{"year":2023,"day":4,"part":2,"answer":null,"status":"error","error":"bad <input>","duration_ms":2.0}"#
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let output = render(Format::Json, &results());
+        let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(value.as_array().unwrap().len(), 2);
+        assert_eq!(value[1]["status"], "error");
+    }
+
+    #[test]
+    fn test_control_characters() {
+        let results = vec![result(Part::One, Status::Error, None, Some("a\u{1}b\tc\r"))];
+        let output = render(Format::Json, &results);
+        assert!(output.contains(r#""error": "a\u0001b\tc\r""#), "{}", output);
+        assert!(
+            render(Format::Junit, &results).contains("<error message=\"a\u{fffd}b\tc\u{fffd}\"/>")
+        );
+    }
+
+    /// Output whose reader has gone away.
+    struct Closed;
+
+    impl Write for Closed {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::BrokenPipe.into())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_closed_output() {
+        let mut json = reporter(Format::Json, Closed);
+        json.report(&results()[0]).unwrap();
+        assert!(json.finish().is_ok());
+        assert!(reporter(Format::Text, Closed)
+            .report(&results()[0])
+            .is_err());
+    }
+
+    #[test]
+    fn test_panic() {
+        let panic = vec![RunResult {
+            backtrace: Some("   0: aoc_2023::day_04::part2\n   1: <&F>::call".to_string()),
+            ..result(Part::Two, Status::Panic, None, Some("boom at src/day_04.rs:1:2"))
+        }];
+        assert_eq!(
+            render(Format::Text, &panic),
+            "PANIC   2023 day 04 part 2: boom at src/day_04.rs:1:2\n           \
+             0: aoc_2023::day_04::part2\n           1: <&F>::call\n"
+        );
+        let output = render(Format::Junit, &panic);
+        assert!(output.contains(r#"tests="1" failures="0" errors="1""#));
+        assert!(output.contains(r#"type="panic">   0: aoc_2023::day_04::part2"#));
+        assert!(output.contains("&lt;&amp;F&gt;::call</error>"));
+    }
+
+    #[test]
+    fn test_limits() {
+        let results = vec![
+            result(Part::One, Status::Timeout, None, Some("ran past 1s")),
+            result(Part::Two, Status::MemoryExceeded, None, Some("ran past 1024 bytes")),
+        ];
+        assert_eq!(
+            render(Format::Text, &results),
+            "TIMEOUT 2023 day 04 part 1: ran past 1s\nMEMORY  2023 day 04 part 2: ran past 1024 bytes\n"
+        );
+        let output = render(Format::Junit, &results);
+        assert!(output.contains(r#"tests="2" failures="0" errors="2""#));
+        assert!(output.contains(r#"<error message="ran past 1s" type="timeout"/>"#));
+        assert!(output.contains(r#"type="memory"/>"#));
+    }
+
+    #[test]
+    fn test_junit() {
+        let output = render(Format::Junit, &results());
+        assert!(output.contains(r#"<testsuite name="aoc-2023" tests="2" failures="0" errors="1""#));
+        assert!(output.contains(r#"<testcase classname="aoc-2023.day_04" name="part 1" time="0.002000">"#));
+        assert!(output.contains(r#"<error message="bad &lt;input&gt;"/>"#));
+    }
+}
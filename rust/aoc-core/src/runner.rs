@@ -0,0 +1,445 @@
+use std::any::Any;
+use std::backtrace::Backtrace;
+use std::cell::{Cell, RefCell};
+use std::fmt::Display;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use tracing::{debug, info_span};
+
+use crate::{Answer, DynSolution, Input, Params, Part};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    /// The part produced an answer, with nothing to compare it to.
+    Ok,
+    /// The answer matches the expected one.
+    Pass,
+    /// The answer differs from the expected one.
+    Fail,
+    /// There was nothing to check, e.g. no input or no known answer.
+    Missing,
+    Error,
+    /// The part, or the parsing of its input, panicked.
+    Panic,
+    /// The part ran past its time limit.
+    Timeout,
+    /// The part ran past its memory limit.
+    MemoryExceeded,
+}
+
+impl Status {
+    pub fn is_failure(self) -> bool {
+        matches!(
+            self,
+            Status::Fail | Status::Error | Status::Panic | Status::Timeout | Status::MemoryExceeded
+        )
+    }
+}
+
+fn serialize_ms<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(duration.as_secs_f64() * 1000.0)
+}
+
+fn deserialize_ms<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+    let ms = f64::deserialize(deserializer)?;
+    Ok(Duration::from_secs_f64(ms.max(0.0) / 1000.0))
+}
+
+/// Result of running one part of a day.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RunResult {
+    pub year: u32,
+    pub day: u32,
+    pub part: Part,
+    /// Name of the example the part ran on, if not the real input.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub example: Option<String>,
+    pub answer: Option<Answer>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected: Option<Answer>,
+    pub status: Status,
+    pub error: Option<String>,
+    /// Where a panic happened, innermost frame first.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backtrace: Option<String>,
+    /// The value of each parameter of the day, given or default.
+    #[serde(default, skip_serializing_if = "Params::is_empty")]
+    pub params: Params,
+    #[serde(
+        rename = "duration_ms",
+        serialize_with = "serialize_ms",
+        deserialize_with = "deserialize_ms"
+    )]
+    pub duration: Duration,
+}
+
+impl RunResult {
+    pub(crate) fn new(solution: &dyn DynSolution, part: Part, status: Status) -> Self {
+        Self {
+            year: solution.year(),
+            day: solution.day(),
+            part,
+            example: None,
+            answer: None,
+            expected: None,
+            status,
+            error: None,
+            backtrace: None,
+            params: Params::new(),
+            duration: Duration::ZERO,
+        }
+    }
+
+    /// A failed part. The error is written with its causes, as `{:#}` formats an `anyhow::Error`.
+    pub fn error(solution: &dyn DynSolution, part: Part, error: impl Display) -> Self {
+        Self {
+            error: Some(format!("{:#}", error)),
+            ..Self::new(solution, part, Status::Error)
+        }
+    }
+
+    pub fn panic(solution: &dyn DynSolution, part: Part, panic: &Panic) -> Self {
+        Self {
+            error: Some(panic.message.clone()),
+            backtrace: Some(panic.backtrace.clone()),
+            ..Self::new(solution, part, Status::Panic)
+        }
+    }
+
+    pub fn missing(solution: &dyn DynSolution, part: Part, reason: impl ToString) -> Self {
+        Self {
+            error: Some(reason.to_string()),
+            ..Self::new(solution, part, Status::Missing)
+        }
+    }
+
+    /// Human readable name of the part, e.g. `2023 day 04 example_1 part 2`.
+    pub fn label(&self) -> String {
+        match &self.example {
+            Some(example) => format!(
+                "{} day {:02} {} part {}",
+                self.year, self.day, example, self.part
+            ),
+            None => format!("{} day {:02} part {}", self.year, self.day, self.part),
+        }
+    }
+
+    /// Compares a computed answer with the expected one, if there is one.
+    pub fn check(self, expected: Option<Answer>) -> Self {
+        if self.status != Status::Ok {
+            return self;
+        }
+        let status = match (&expected, &self.answer) {
+            (None, _) => Status::Missing,
+            (Some(expected), Some(answer)) if expected == answer => Status::Pass,
+            _ => Status::Fail,
+        };
+        let error = (status == Status::Missing).then(|| "no known answer".to_string());
+        Self {
+            expected,
+            status,
+            error,
+            ..self
+        }
+    }
+}
+
+/// A panic caught by [`catch_panic`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Panic {
+    /// The panic message and where it was raised.
+    pub message: String,
+    pub backtrace: String,
+}
+
+thread_local! {
+    /// Whether panics on this thread are being caught, and so recorded rather than printed.
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    static CAUGHT: RefCell<Option<Panic>> = const { RefCell::new(None) };
+}
+
+/// Installs, once, a panic hook recording the panics of threads inside [`catch_panic`]. Panics
+/// elsewhere still go to the previous hook.
+fn install_hook() {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !CATCHING.get() {
+                return previous(info);
+            }
+            let mut message = payload_message(info.payload());
+            if let Some(location) = info.location() {
+                message = format!("{} at {}", message, location);
+            }
+            let backtrace = trim_backtrace(&Backtrace::force_capture().to_string());
+            CAUGHT.set(Some(Panic { message, backtrace }));
+        }));
+    });
+}
+
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+        (Some(s), _) => s.to_string(),
+        (_, Some(s)) => s.clone(),
+        _ => "panicked".to_string(),
+    }
+}
+
+/// The function of a backtrace frame, given as its lines.
+fn frame_symbol<'a>(frame: &[&'a str]) -> &'a str {
+    frame[0].split_once(':').map_or("", |(_, s)| s.trim())
+}
+
+/// Keeps the frames of a backtrace between the panic machinery and [`catch_panic`], which are the
+/// ones of the solution. The whole backtrace is kept if they cannot be told apart.
+fn trim_backtrace(backtrace: &str) -> String {
+    let mut frames: Vec<Vec<&str>> = vec![];
+    for line in backtrace.lines() {
+        let is_frame = line
+            .trim_start()
+            .split_once(':')
+            .is_some_and(|(n, _)| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()));
+        match frames.last_mut() {
+            Some(frame) if !is_frame => frame.push(line),
+            _ => frames.push(vec![line]),
+        }
+    }
+    let end = frames
+        .iter()
+        .position(|f| {
+            let s = frame_symbol(f);
+            s.contains("AssertUnwindSafe") || s.contains("catch_unwind")
+        })
+        .unwrap_or(frames.len());
+    let start = frames[..end]
+        .iter()
+        .rposition(|f| {
+            let s = frame_symbol(f);
+            s.starts_with("std::panicking")
+                || s.starts_with("core::panicking")
+                || s.contains("rust_begin_unwind")
+        })
+        .map_or(0, |i| i + 1);
+    if start >= end {
+        return backtrace.to_string();
+    }
+    frames[start..end]
+        .iter()
+        .flatten()
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Runs `f`, turning a panic into an error carrying its message and backtrace. The panic is not
+/// printed.
+pub fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, Panic> {
+    install_hook();
+    let catching = CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(catching);
+    result.map_err(|payload| {
+        CAUGHT.take().unwrap_or_else(|| Panic {
+            message: payload_message(payload.as_ref()),
+            backtrace: String::new(),
+        })
+    })
+}
+
+/// Parses `input` once, then runs and times each of `parts`. A panic is reported as the result of
+/// the part it happened in, or of every part if it happened while parsing.
+pub fn run(solution: &dyn DynSolution, input: &Input, parts: &[Part]) -> Vec<RunResult> {
+    let mut results = vec![];
+    run_each(solution, input, parts, |result| results.push(result));
+    results
+}
+
+/// Like [`run`], but hands each result to `emit` as soon as its part is done.
+pub fn run_each(
+    solution: &dyn DynSolution,
+    input: &Input,
+    parts: &[Part],
+    mut emit: impl FnMut(RunResult),
+) {
+    let _day = info_span!("day", year = solution.year(), day = solution.day()).entered();
+    let params = match input.params().resolve(solution.params()) {
+        Ok(params) => params,
+        Err(e) => {
+            for &part in parts {
+                emit(RunResult::error(solution, part, &e));
+            }
+            return;
+        }
+    };
+    let mut emit = |result| {
+        emit(RunResult {
+            params: params.clone(),
+            ..result
+        })
+    };
+    let start = Instant::now();
+    let parsed = info_span!("parse").in_scope(|| catch_panic(|| solution.parse(input)));
+    debug!(elapsed = ?start.elapsed(), "parsed the input");
+    let model = match parsed {
+        Ok(Ok(model)) => model,
+        parsed => {
+            let duration = start.elapsed();
+            for &part in parts {
+                let result = match &parsed {
+                    Err(panic) => RunResult::panic(solution, part, panic),
+                    Ok(Err(e)) => RunResult::error(solution, part, e),
+                    Ok(Ok(_)) => unreachable!(),
+                };
+                emit(RunResult { duration, ..result });
+            }
+            return;
+        }
+    };
+    for &part in parts {
+        let _part = info_span!("part", part = %part).entered();
+        let start = Instant::now();
+        let answer = catch_panic(|| solution.solve(model.as_ref(), part));
+        let duration = start.elapsed();
+        debug!(elapsed = ?duration, "solved");
+        emit(match answer {
+            Ok(Ok(answer)) => RunResult {
+                answer: Some(answer),
+                duration,
+                ..RunResult::new(solution, part, Status::Ok)
+            },
+            Ok(Err(e)) => RunResult {
+                duration,
+                ..RunResult::error(solution, part, e)
+            },
+            Err(panic) => RunResult {
+                duration,
+                ..RunResult::panic(solution, part, &panic)
+            },
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::{bail, Result};
+
+    use super::*;
+    use crate::{solution, Param, Solution};
+
+    struct Fragile;
+
+    impl Solution for Fragile {
+        const YEAR: u32 = 2000;
+        const DAY: u32 = 2;
+
+        type Model = Vec<u32>;
+        type Answer = u32;
+
+        fn parse(input: &Input) -> Result<Vec<u32>> {
+            Ok(input.split(',').map(|s| s.parse().unwrap()).collect())
+        }
+
+        fn part1(model: &Vec<u32>) -> Result<u32> {
+            Ok(model[3])
+        }
+
+        fn part2(model: &Vec<u32>) -> Result<u32> {
+            match model.first() {
+                Some(0) => bail!("no zeros"),
+                _ => Ok(model.iter().sum()),
+            }
+        }
+    }
+
+    struct Scaled;
+
+    const FACTOR: Param = Param::new("factor", "2", "what the input is multiplied by");
+
+    impl Solution for Scaled {
+        const YEAR: u32 = 2000;
+        const DAY: u32 = 3;
+        const PARAMS: &'static [Param] = &[FACTOR];
+
+        type Model = u32;
+        type Answer = u32;
+
+        fn parse(input: &Input) -> Result<u32> {
+            Ok(input.trim().parse::<u32>()? * input.param::<u32>(&FACTOR)?)
+        }
+
+        fn part1(model: &u32) -> Result<u32> {
+            Ok(*model)
+        }
+
+        fn part2(model: &u32) -> Result<u32> {
+            Ok(model + 1)
+        }
+    }
+
+    #[test]
+    fn test_run_params() {
+        let s = solution::<Scaled>();
+        let results = run(s.as_ref(), &Input::new("5"), &[Part::One]);
+        assert_eq!(results[0].answer, Some(Answer::UInt(10)));
+        assert_eq!(results[0].params.get("factor"), Some("2"));
+
+        let input = Input::new("5").with_params([("factor", "3")].into_iter().collect());
+        let results = run(s.as_ref(), &input, &Part::ALL);
+        assert_eq!(results[1].answer, Some(Answer::UInt(16)));
+        assert_eq!(results[1].params.get("factor"), Some("3"));
+
+        let input = Input::new("5").with_params([("scale", "3")].into_iter().collect());
+        let results = run(s.as_ref(), &input, &Part::ALL);
+        assert!(results.iter().all(|r| r.status == Status::Error));
+        assert!(results[0].error.as_ref().unwrap().contains("\"scale\""));
+    }
+
+    #[test]
+    fn test_error_causes() {
+        let s = solution::<Fragile>();
+        let error = anyhow::anyhow!("no zeros").context("reading the model");
+        let result = RunResult::error(s.as_ref(), Part::One, &error);
+        assert_eq!(result.error.as_deref(), Some("reading the model: no zeros"));
+        assert_eq!(result.status, Status::Error);
+    }
+
+    #[test]
+    fn test_catch_panic() {
+        assert_eq!(catch_panic(|| 1), Ok(1));
+        let panic = catch_panic(|| -> u32 { panic!("boom {}", 1) }).unwrap_err();
+        assert!(panic.message.starts_with("boom 1 at "));
+        assert!(panic.message.contains("runner.rs"));
+        assert!(panic.backtrace.contains("test_catch_panic"));
+        assert!(!panic.backtrace.contains("panicking"));
+        assert!(!panic.backtrace.contains("catch_unwind"));
+    }
+
+    #[test]
+    fn test_run_panics() {
+        let s = solution::<Fragile>();
+        let results = run(s.as_ref(), &Input::new("1,2"), &Part::ALL);
+        assert_eq!(results[0].status, Status::Panic);
+        assert!(results[0]
+            .error
+            .as_ref()
+            .unwrap()
+            .starts_with("index out of bounds"));
+        assert!(results[0].backtrace.is_some());
+        assert_eq!(results[1].status, Status::Ok);
+        assert_eq!(results[1].answer, Some(Answer::UInt(3)));
+
+        let results = run(s.as_ref(), &Input::new("0,1,2,3"), &Part::ALL);
+        assert_eq!(results[0].answer, Some(Answer::UInt(3)));
+        assert_eq!(results[1].status, Status::Error);
+
+        let results = run(s.as_ref(), &Input::new("1,x"), &Part::ALL);
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|r| r.status == Status::Panic));
+        assert!(results[0].error.as_ref().unwrap().contains("ParseIntError"));
+    }
+}
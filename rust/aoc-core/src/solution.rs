@@ -0,0 +1,168 @@
+use std::any::Any;
+use std::fmt::{self, Display};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Error, Result};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Answer, Input, Param};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+impl Serialize for Part {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Part::One => serializer.serialize_u8(1),
+            Part::Two => serializer.serialize_u8(2),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Part {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match u8::deserialize(deserializer)? {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            n => Err(de::Error::custom(format!("invalid part {}, expected 1 or 2", n))),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim() {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => bail!("Invalid part {:?}, expected 1 or 2", s),
+        }
+    }
+}
+
+/// A single puzzle day: a parse step producing a model, and two parts solved from it.
+pub trait Solution {
+    const YEAR: u32;
+    const DAY: u32;
+
+    /// Parameters the day reads from its input with [`Input::param`].
+    const PARAMS: &'static [Param] = &[];
+
+    type Model: 'static;
+    type Answer: Into<Answer>;
+
+    fn parse(input: &Input) -> Result<Self::Model>;
+    fn part1(model: &Self::Model) -> Result<Self::Answer>;
+    fn part2(model: &Self::Model) -> Result<Self::Answer>;
+}
+
+/// Object-safe view of a [`Solution`], so days of every year can be stored and run together.
+pub trait DynSolution: Send + Sync {
+    fn year(&self) -> u32;
+    fn day(&self) -> u32;
+    fn params(&self) -> &'static [Param];
+    /// An owned copy, e.g. to run the solution on another thread.
+    fn clone_box(&self) -> Box<dyn DynSolution>;
+    fn parse(&self, input: &Input) -> Result<Box<dyn Any>>;
+    fn part1(&self, model: &dyn Any) -> Result<Answer>;
+    fn part2(&self, model: &dyn Any) -> Result<Answer>;
+
+    fn solve(&self, model: &dyn Any, part: Part) -> Result<Answer> {
+        match part {
+            Part::One => self.part1(model),
+            Part::Two => self.part2(model),
+        }
+    }
+}
+
+struct Erased<S>(PhantomData<fn() -> S>);
+
+impl<S: Solution> Erased<S> {
+    fn model<'a>(&self, model: &'a dyn Any) -> Result<&'a S::Model> {
+        model
+            .downcast_ref::<S::Model>()
+            .ok_or_else(|| anyhow!("Model type mismatch for {} day {:02}", S::YEAR, S::DAY))
+    }
+}
+
+impl<S: Solution + 'static> DynSolution for Erased<S> {
+    fn year(&self) -> u32 {
+        S::YEAR
+    }
+
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn params(&self) -> &'static [Param] {
+        S::PARAMS
+    }
+
+    fn clone_box(&self) -> Box<dyn DynSolution> {
+        solution::<S>()
+    }
+
+    fn parse(&self, input: &Input) -> Result<Box<dyn Any>> {
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn part1(&self, model: &dyn Any) -> Result<Answer> {
+        Ok(S::part1(self.model(model)?)?.into())
+    }
+
+    fn part2(&self, model: &dyn Any) -> Result<Answer> {
+        Ok(S::part2(self.model(model)?)?.into())
+    }
+}
+
+/// Box a [`Solution`] as a [`DynSolution`].
+pub fn solution<S: Solution + 'static>() -> Box<dyn DynSolution> {
+    Box::new(Erased::<S>(PhantomData))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::Sum;
+
+    #[test]
+    fn test_dyn_solution() {
+        let s = solution::<Sum>();
+        assert_eq!((s.year(), s.day()), (2000, 1));
+        let model = s.parse(&Input::new("1,2,3,4")).unwrap();
+        assert_eq!(s.part1(model.as_ref()).unwrap(), Answer::UInt(10));
+        assert_eq!(s.part2(model.as_ref()).unwrap(), Answer::UInt(24));
+        assert_eq!(s.solve(model.as_ref(), Part::Two).unwrap(), Answer::Int(24));
+    }
+
+    #[test]
+    fn test_part_from_str() {
+        assert_eq!("1".parse::<Part>().unwrap(), Part::One);
+        assert_eq!("2".parse::<Part>().unwrap(), Part::Two);
+        assert!("3".parse::<Part>().is_err());
+    }
+
+    #[test]
+    fn test_model_mismatch() {
+        let s = solution::<Sum>();
+        assert!(s.part1(&"not a model").is_err());
+    }
+}
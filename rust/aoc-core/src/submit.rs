@@ -0,0 +1,431 @@
+use std::env;
+use std::fmt::{self, Display};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::fetch::{
+    self, HttpClient, UreqClient, BASE_URL, BASE_URL_VAR, CACHE_DIR_VAR, MIN_INTERVAL,
+};
+use crate::{Answer, Part};
+
+pub const ATTEMPTS_FILE: &str = "attempts.toml";
+
+/// What the site said about a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint.
+    Wrong,
+    /// Submitted too soon after a wrong answer, the answer was not checked.
+    Wait,
+    /// The part was already solved on the site, the answer was not checked.
+    AlreadySolved,
+}
+
+impl Verdict {
+    /// Whether the site actually checked the answer.
+    pub fn is_checked(self) -> bool {
+        !matches!(self, Verdict::Wait | Verdict::AlreadySolved)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::Wait => write!(f, "submitted too soon"),
+            Verdict::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+/// The text of the `<article>` element of a response page, without markup.
+fn article_text(body: &str) -> String {
+    let article = body
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(body, |(article, _)| article);
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Reads the verdict out of the page returned for a submitted answer.
+pub fn parse_response(body: &str) -> Result<(Verdict, String)> {
+    let text = article_text(body);
+    let verdict = if text.contains("That's the right answer") {
+        Verdict::Correct
+    } else if text.contains("your answer is too high") {
+        Verdict::TooHigh
+    } else if text.contains("your answer is too low") {
+        Verdict::TooLow
+    } else if text.contains("That's not the right answer") {
+        Verdict::Wrong
+    } else if text.contains("You gave an answer too recently") {
+        Verdict::Wait
+    } else if text.contains("You don't seem to be solving the right level") {
+        Verdict::AlreadySolved
+    } else {
+        bail!("Unrecognized response: {:?}", text);
+    };
+    Ok((verdict, text))
+}
+
+/// One submitted answer.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub part: Part,
+    pub answer: Answer,
+    pub verdict: Verdict,
+    /// Seconds since the Unix epoch.
+    pub time: u64,
+    /// The text of the response.
+    pub message: String,
+}
+
+/// Every answer submitted for a day, stored as `[[attempt]]` tables.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct History {
+    #[serde(default, rename = "attempt")]
+    pub attempts: Vec<Attempt>,
+}
+
+impl History {
+    /// Loads a history file, empty if it does not exist yet.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content =
+            fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Invalid attempts file {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        fs::write(path, toml::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    fn checked(&self, part: Part) -> impl Iterator<Item = &Attempt> {
+        self.attempts
+            .iter()
+            .filter(move |a| a.part == part && a.verdict.is_checked())
+    }
+
+    /// The accepted answer of a part, if any.
+    pub fn correct(&self, part: Part) -> Option<&Answer> {
+        self.checked(part)
+            .find(|a| a.verdict == Verdict::Correct)
+            .map(|a| &a.answer)
+    }
+
+    /// The exclusive bounds of a part's answer known from too-low and too-high hints.
+    pub fn bounds(&self, part: Part) -> (Option<i128>, Option<i128>) {
+        let hints = |verdict| {
+            self.checked(part)
+                .filter(move |a| a.verdict == verdict)
+                .filter_map(|a| i128::try_from(a.answer.to_int()?).ok())
+        };
+        (hints(Verdict::TooLow).max(), hints(Verdict::TooHigh).min())
+    }
+
+    /// Fails if submitting `answer` is pointless given the earlier attempts.
+    pub fn check(&self, part: Part, answer: &Answer) -> Result<()> {
+        if let Some(correct) = self.correct(part) {
+            bail!("Part {} was already solved with {}", part, correct);
+        }
+        if let Some(attempt) = self.checked(part).find(|a| a.answer == *answer) {
+            bail!("{} was already submitted and is {}", answer, attempt.verdict);
+        }
+        if let Some(n) = answer.to_int().and_then(|n| i128::try_from(n).ok()) {
+            match self.bounds(part) {
+                (Some(low), _) if n <= low => {
+                    bail!("{} is too low, the answer is above {}", answer, low)
+                }
+                (_, Some(high)) if n >= high => {
+                    bail!("{} is too high, the answer is below {}", answer, high)
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Submits answers to the site, keeping every attempt next to the day's cached input.
+pub struct Submitter {
+    client: Box<dyn HttpClient>,
+    base_url: String,
+    session: Option<String>,
+    cache_dir: PathBuf,
+    min_interval: Duration,
+}
+
+impl Submitter {
+    pub fn new(client: impl HttpClient + 'static, cache_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            client: Box::new(client),
+            base_url: BASE_URL.to_string(),
+            session: None,
+            cache_dir: cache_dir.into(),
+            min_interval: MIN_INTERVAL,
+        }
+    }
+
+    /// A submitter using the default backend, configured like [`fetch::Fetcher::from_env`].
+    pub fn from_env() -> Result<Self> {
+        let Some(cache_dir) = fetch::cache_dir() else {
+            bail!("No cache directory, set {} or HOME", CACHE_DIR_VAR);
+        };
+        let mut submitter = Self::new(UreqClient::default(), cache_dir);
+        submitter.session = fetch::session()?;
+        if let Ok(url) = env::var(BASE_URL_VAR) {
+            submitter = submitter.with_base_url(url);
+        }
+        Ok(submitter)
+    }
+
+    pub fn with_base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_session(mut self, session: impl Into<String>) -> Self {
+        self.session = Some(session.into());
+        self
+    }
+
+    pub fn with_min_interval(mut self, interval: Duration) -> Self {
+        self.min_interval = interval;
+        self
+    }
+
+    pub fn history_path(&self, year: u32, day: u32) -> PathBuf {
+        self.cache_dir
+            .join(year.to_string())
+            .join(format!("day_{:02}", day))
+            .join(ATTEMPTS_FILE)
+    }
+
+    pub fn history(&self, year: u32, day: u32) -> Result<History> {
+        History::load(&self.history_path(year, day))
+    }
+
+    /// Submits an answer unless the earlier attempts already rule it out, and records it.
+    pub fn submit(&self, year: u32, day: u32, part: Part, answer: &Answer) -> Result<Attempt> {
+        if answer.to_string().trim().is_empty() {
+            bail!("Refusing to submit an empty answer");
+        }
+        let path = self.history_path(year, day);
+        let mut history = History::load(&path)?;
+        history.check(part, answer)?;
+        let Some(session) = &self.session else {
+            bail!("No session token to submit answers, set {}", fetch::SESSION_VAR);
+        };
+
+        fetch::wait_turn(&self.cache_dir, self.min_interval)?;
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        let (level, value) = (part.to_string(), answer.to_string());
+        let response = self
+            .client
+            .post(&url, session, &[("level", &level), ("answer", &value)])?;
+        if response.status != 200 {
+            bail!("Unexpected status {} from {}", response.status, url);
+        }
+        let (verdict, message) = parse_response(&response.body)?;
+
+        let attempt = Attempt {
+            part,
+            answer: answer.clone(),
+            verdict,
+            time: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+            message,
+        };
+        history.attempts.push(attempt.clone());
+        history.save(&path)?;
+        Ok(attempt)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::fetch::test::serve_once;
+    use crate::fetch::Response;
+
+    const TOO_HIGH: &str = "<html><body><main><article><p>That's not the right answer; \
+        your answer is too high.  If you're stuck, make sure you're using the full input data. \
+        <a href=\"/2020/day/1\">[Return to Day 1]</a></p></article></main></body></html>";
+    const TOO_LOW: &str = "<main><article><p>That's not the right answer; your answer is \
+        too low.</p></article></main>";
+    const CORRECT: &str = "<main><article><p>That's the right answer!  You are <span \
+        class=\"day-success\">one gold star</span> closer to saving your vacation.</p></article>\
+        </main>";
+    const WAIT: &str = "<main><article><p>You gave an answer too recently; you have to wait \
+        after submitting an answer before trying again.  You have 41s left to wait.</p>\
+        </article></main>";
+
+    /// Answers submissions with the given pages in turn, recording the forms.
+    #[derive(Clone, Default)]
+    struct FakeSite {
+        pages: Rc<RefCell<Vec<&'static str>>>,
+        forms: Rc<RefCell<Vec<(String, String)>>>,
+    }
+
+    impl HttpClient for FakeSite {
+        fn get(&self, _: &str, _: &str) -> Result<Response> {
+            unreachable!("answers are only ever posted")
+        }
+
+        fn post(&self, url: &str, session: &str, form: &[(&str, &str)]) -> Result<Response> {
+            assert_eq!(url, "https://adventofcode.com/2020/day/1/answer");
+            assert_eq!(session, "secret");
+            self.forms
+                .borrow_mut()
+                .push((form[0].1.to_string(), form[1].1.to_string()));
+            Ok(Response {
+                status: 200,
+                body: self.pages.borrow_mut().remove(0).to_string(),
+            })
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-submit-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let (verdict, message) = parse_response(TOO_HIGH).unwrap();
+        assert_eq!(verdict, Verdict::TooHigh);
+        assert!(message.starts_with("That's not the right answer; your answer is too high."));
+        assert!(message.ends_with("[Return to Day 1]"));
+        assert_eq!(parse_response(TOO_LOW).unwrap().0, Verdict::TooLow);
+        assert_eq!(parse_response(CORRECT).unwrap().0, Verdict::Correct);
+        assert_eq!(parse_response(WAIT).unwrap().0, Verdict::Wait);
+        assert!(parse_response("<article><p>Huh?</p></article>").is_err());
+    }
+
+    fn attempt(part: Part, answer: &str, verdict: Verdict) -> Attempt {
+        Attempt {
+            part,
+            answer: answer.into(),
+            verdict,
+            time: 0,
+            message: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_history_check() {
+        let history = History {
+            attempts: vec![
+                attempt(Part::One, "100", Verdict::TooLow),
+                attempt(Part::One, "150", Verdict::TooLow),
+                attempt(Part::One, "300", Verdict::TooHigh),
+                attempt(Part::One, "200", Verdict::Wrong),
+                attempt(Part::One, "250", Verdict::Wait),
+                attempt(Part::Two, "abc", Verdict::Correct),
+            ],
+        };
+        assert_eq!(history.bounds(Part::One), (Some(150), Some(300)));
+        assert_eq!(history.bounds(Part::Two), (None, None));
+        assert!(history.check(Part::One, &"120".into()).is_err());
+        assert!(history.check(Part::One, &"150".into()).is_err());
+        assert!(history.check(Part::One, &"301".into()).is_err());
+        assert!(history.check(Part::One, &"200".into()).is_err());
+        assert!(history.check(Part::One, &"250".into()).is_ok());
+        assert!(history.check(Part::One, &"299".into()).is_ok());
+        assert!(history.check(Part::One, &"abc".into()).is_ok());
+        assert_eq!(
+            history.check(Part::Two, &"xyz".into()).unwrap_err().to_string(),
+            "Part 2 was already solved with abc"
+        );
+    }
+
+    #[test]
+    fn test_submit() {
+        let dir = temp_dir("submit");
+        let site = FakeSite::default();
+        site.pages.borrow_mut().extend([TOO_HIGH, TOO_LOW, CORRECT]);
+        let submitter = Submitter::new(site.clone(), &dir)
+            .with_session("secret")
+            .with_min_interval(Duration::ZERO);
+
+        assert_eq!(
+            submitter.submit(2020, 1, Part::One, &"900".into()).unwrap().verdict,
+            Verdict::TooHigh
+        );
+        // Blocked before sending.
+        assert!(submitter.submit(2020, 1, Part::One, &"900".into()).is_err());
+        assert!(submitter.submit(2020, 1, Part::One, &"1000".into()).is_err());
+        assert_eq!(
+            submitter.submit(2020, 1, Part::One, &"100".into()).unwrap().verdict,
+            Verdict::TooLow
+        );
+        assert_eq!(
+            submitter.submit(2020, 1, Part::One, &" 514\n".into()).unwrap().verdict,
+            Verdict::Correct
+        );
+        assert!(submitter.submit(2020, 1, Part::One, &"515".into()).is_err());
+
+        assert_eq!(
+            *site.forms.borrow(),
+            vec![
+                ("1".to_string(), "900".to_string()),
+                ("1".to_string(), "100".to_string()),
+                ("1".to_string(), "514".to_string()),
+            ]
+        );
+        let history = submitter.history(2020, 1).unwrap();
+        assert_eq!(history.attempts.len(), 3);
+        assert_eq!(history.correct(Part::One), Some(&Answer::UInt(514)));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_ureq_client() {
+        let (url, server) = serve_once(CORRECT);
+        let dir = temp_dir("ureq");
+        let attempt = Submitter::new(UreqClient::default(), &dir)
+            .with_base_url(url)
+            .with_session("secret")
+            .with_min_interval(Duration::ZERO)
+            .submit(2020, 1, Part::Two, &Answer::UInt(241861950))
+            .unwrap();
+        assert_eq!(attempt.verdict, Verdict::Correct);
+        let (head, body) = server.join().unwrap();
+        assert_eq!(head[0], "POST /2020/day/1/answer HTTP/1.1");
+        assert!(head.iter().any(|h| h == "Cookie: session=secret"));
+        assert_eq!(body, "level=2&answer=241861950");
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,70 @@
+//! Helpers shared by tests, of this crate and of the year crates, which enable the `testing`
+//! feature in their dev-dependencies.
+
+use anyhow::Result;
+
+use crate::examples;
+use crate::input::default_path;
+use crate::runner::{RunResult, Status};
+use crate::{read_input, DynSolution, Input, Solution};
+
+/// A xorshift generator, enough to make test inputs.
+pub struct Rng(pub u64);
+
+impl Rng {
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 % n
+    }
+}
+
+/// A day reading comma-separated numbers, whose part 1 adds them up and part 2 multiplies them.
+pub struct Sum;
+
+impl Solution for Sum {
+    const YEAR: u32 = 2000;
+    const DAY: u32 = 1;
+
+    type Model = Vec<u32>;
+    type Answer = u32;
+
+    fn parse(input: &Input) -> Result<Vec<u32>> {
+        Ok(input.split(',').map(str::parse).collect::<Result<_, _>>()?)
+    }
+
+    fn part1(model: &Vec<u32>) -> Result<u32> {
+        Ok(model.iter().sum())
+    }
+
+    fn part2(model: &Vec<u32>) -> Result<u32> {
+        Ok(model.iter().product())
+    }
+}
+
+pub fn statuses(results: &[RunResult]) -> Vec<Status> {
+    results.iter().map(|r| r.status).collect()
+}
+
+/// The puzzle input of a day, or `None` when there is none. Inputs are private, so checks of the
+/// answers skip a day whose input is missing rather than fail.
+pub fn puzzle_input(year: u32, day: u32) -> Option<Input> {
+    match default_path(year, day) {
+        Ok(_) => Some(read_input(year, day).unwrap()),
+        Err(e) => {
+            eprintln!("Skipping the check: {}", e);
+            None
+        }
+    }
+}
+
+/// Checks the answers of every example of `solutions`, so a day scaffolded without answers yet
+/// passes.
+pub fn check_examples(solutions: &[Box<dyn DynSolution>]) {
+    for solution in solutions {
+        for result in examples::check(solution.as_ref()).unwrap() {
+            assert_eq!(result.status, Status::Pass, "{:?}", result);
+        }
+    }
+}
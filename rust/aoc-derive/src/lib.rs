@@ -0,0 +1,179 @@
+//! `#[derive(AocParse)]`, re-exported as `aoc_core::pattern::AocParse`.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, LitStr, Result};
+
+/// Implements `AocParse` and `FromStr` for a struct with named fields, from a pattern such as
+/// `#[aoc(pattern = "{first}-{second} {character}: {password}")]`.
+#[proc_macro_derive(AocParse, attributes(aoc))]
+pub fn derive_aoc_parse(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+enum Piece {
+    Literal(String),
+    Field(String),
+}
+
+/// Splits a pattern into literals and `{field}` placeholders; `{{` and `}}` are literal braces.
+fn split_pattern(lit: &LitStr) -> Result<Vec<Piece>> {
+    let pattern = lit.value();
+    let error = |message: &str| Error::new(lit.span(), message);
+    let mut pieces = vec![];
+    let mut literal = String::new();
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let name: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                    return Err(error("expected a field name between `{` and `}`"));
+                }
+                if literal.is_empty() && matches!(pieces.last(), Some(Piece::Field(_))) {
+                    return Err(error("placeholders must be separated by some text"));
+                }
+                if !literal.is_empty() {
+                    pieces.push(Piece::Literal(std::mem::take(&mut literal)));
+                }
+                pieces.push(Piece::Field(name));
+            }
+            '}' => return Err(error("unmatched `}`, write `}}` for a literal one")),
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        pieces.push(Piece::Literal(literal));
+    }
+    Ok(pieces)
+}
+
+/// The `pattern` of the `#[aoc(...)]` attribute on the struct.
+fn pattern_attr(input: &DeriveInput) -> Result<LitStr> {
+    let mut pattern = None;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("aoc")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("pattern") {
+                pattern = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `pattern = \"...\"`"))
+            }
+        })?;
+    }
+    pattern.ok_or_else(|| {
+        Error::new(
+            Span::call_site(),
+            "expected a #[aoc(pattern = \"...\")] attribute",
+        )
+    })
+}
+
+/// Whether a field is marked `#[aoc(flatten)]`.
+fn is_flatten(field: &syn::Field) -> Result<bool> {
+    let mut flatten = false;
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("aoc")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("flatten") {
+                flatten = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `flatten`"))
+            }
+        })?;
+    }
+    Ok(flatten)
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(
+            Span::call_site(),
+            "AocParse can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new(
+            Span::call_site(),
+            "AocParse needs a struct with named fields",
+        ));
+    };
+    let lit = pattern_attr(&input)?;
+    let pieces = split_pattern(&lit)?;
+    let mut placeholders: Vec<&str> = vec![];
+    for piece in &pieces {
+        if let Piece::Field(name) = piece {
+            if placeholders.contains(&name.as_str()) {
+                return Err(Error::new(
+                    lit.span(),
+                    format!("`{{{}}}` appears more than once in the pattern", name),
+                ));
+            }
+            placeholders.push(name);
+        }
+    }
+
+    let mut inits = vec![];
+    let mut names = vec![];
+    for field in &fields.named {
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let name = ident.to_string();
+        if !placeholders.contains(&name.as_str()) {
+            return Err(Error::new(
+                ident.span(),
+                format!("`{{{}}}` is missing from the pattern", name),
+            ));
+        }
+        if is_flatten(field)? {
+            inits.push(quote! { #ident: fields.nested::<#ty>(#name)? });
+        } else {
+            inits.push(quote! { #ident: fields.get::<#ty>(#name)? });
+        }
+        names.push(name);
+    }
+    if let Some(unknown) = placeholders.iter().find(|p| !names.iter().any(|n| n == *p)) {
+        return Err(Error::new(
+            lit.span(),
+            format!("no field named `{}`", unknown),
+        ));
+    }
+    let name = &input.ident;
+
+    let pieces = pieces.iter().map(|piece| match piece {
+        Piece::Literal(s) => quote! { ::aoc_core::pattern::Piece::Literal(#s) },
+        Piece::Field(s) => quote! { ::aoc_core::pattern::Piece::Field(#s) },
+    });
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::aoc_core::pattern::AocParse for #name #ty_generics #where_clause {
+            const PATTERN: &'static [::aoc_core::pattern::Piece] = &[#(#pieces),*];
+
+            fn from_fields(
+                fields: &::aoc_core::pattern::Fields,
+            ) -> ::std::result::Result<Self, ::aoc_core::ParseError> {
+                ::std::result::Result::Ok(Self { #(#inits),* })
+            }
+        }
+
+        impl #impl_generics ::std::str::FromStr for #name #ty_generics #where_clause {
+            type Err = ::aoc_core::ParseError;
+
+            fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                ::aoc_core::pattern::parse(s)
+            }
+        }
+    })
+}
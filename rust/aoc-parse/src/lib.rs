@@ -0,0 +1,251 @@
+//! nom combinators for the shapes puzzle inputs keep coming in: whitespace-separated numbers,
+//! `Label N:` prefixes, `key:value` records, one item per line, blank-line-separated blocks and
+//! character grids.
+//!
+//! Every combinator is generic over the nom error type, so they compose with any parser, and none
+//! of them consumes the line break after the last line, leaving blank lines to [`blocks`].
+
+use nom::branch::alt;
+use nom::bytes::complete::{tag, take_till1, take_while1};
+use nom::character::complete::{char, i64, line_ending, multispace0, none_of, space0, space1, u32};
+use nom::combinator::{cut, map, opt, recognize, verify};
+use nom::error::ParseError;
+use nom::multi::{many1, many1_count, separated_list0, separated_list1};
+use nom::sequence::{delimited, pair, preceded, separated_pair, tuple};
+use nom::{IResult, Parser};
+
+/// A combinator that takes a parser `inner` and produces a parser that also consumes both leading
+/// and trailing whitespace, line breaks included, returning the output of `inner`.
+pub fn ws<'a, F, O, E>(inner: F) -> impl FnMut(&'a str) -> IResult<&'a str, O, E>
+where
+    F: Parser<&'a str, O, E>,
+    E: ParseError<&'a str>,
+{
+    delimited(multispace0, inner, multispace0)
+}
+
+/// Like [`ws`], but only consumes spaces and tabs, so it never runs past the end of a line.
+pub fn spaced<'a, F, O, E>(inner: F) -> impl FnMut(&'a str) -> IResult<&'a str, O, E>
+where
+    F: Parser<&'a str, O, E>,
+    E: ParseError<&'a str>,
+{
+    delimited(space0, inner, space0)
+}
+
+/// Parses a label followed by a number, e.g. `Game 1` or `Card  12`, returning the number.
+pub fn labelled_id<'a, E>(label: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, u32, E>
+where
+    E: ParseError<&'a str>,
+{
+    preceded(pair(tag(label), space1), u32)
+}
+
+/// Parses `Label N: body`, returning the id and the output of `body`.
+pub fn labelled<'a, F, O, E>(
+    label: &'static str,
+    body: F,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (u32, O), E>
+where
+    F: Parser<&'a str, O, E>,
+    E: ParseError<&'a str>,
+{
+    separated_pair(labelled_id(label), spaced(char(':')), body)
+}
+
+/// Parses a possibly empty list of items separated by spaces, e.g. ` 41 48  6 `.
+pub fn list<'a, F, O, E>(item: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>, E>
+where
+    F: Parser<&'a str, O, E>,
+    E: ParseError<&'a str>,
+{
+    spaced(separated_list0(space1, item))
+}
+
+/// Parses a list of unsigned numbers separated by spaces.
+pub fn uints<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Vec<u32>, E> {
+    list(u32)(input)
+}
+
+/// Parses a list of signed numbers separated by spaces.
+pub fn ints<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, Vec<i64>, E> {
+    list(i64)(input)
+}
+
+/// Parses one or more items separated by `sep`, which may have spaces around it, e.g.
+/// `3 blue, 4 red`. Once a separator is found an item must follow it, so a bad item fails the
+/// whole parse where it is instead of ending the list early.
+pub fn separated<'a, F, O, E>(
+    sep: char,
+    mut item: F,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>, E>
+where
+    F: Parser<&'a str, O, E>,
+    E: ParseError<&'a str>,
+{
+    move |input: &'a str| {
+        let (mut input, first) = item.parse(input)?;
+        let mut items = vec![first];
+        while let Ok((rest, _)) = spaced(char::<_, E>(sep))(input) {
+            let (rest, next) = cut(|i| item.parse(i))(rest)?;
+            items.push(next);
+            input = rest;
+        }
+        Ok((input, items))
+    }
+}
+
+/// Parses a `key:value` pair, where the key is a word and the value runs up to the next
+/// whitespace, e.g. `hgt:183cm`.
+pub fn key_value<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, (&'a str, &'a str), E> {
+    separated_pair(
+        take_while1(|c: char| c.is_alphanumeric() || c == '_'),
+        char(':'),
+        take_till1(|c: char| c.is_whitespace()),
+    )(input)
+}
+
+/// Parses `key:value` pairs separated by spaces or single line breaks, so a record can span lines
+/// but ends at a blank line.
+pub fn record<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, Vec<(&'a str, &'a str)>, E> {
+    let sep = verify(
+        recognize(tuple((space0, opt(line_ending), space0))),
+        |s: &str| !s.is_empty(),
+    );
+    separated_list1(sep, key_value)(input)
+}
+
+/// Parses one item per line.
+pub fn lines<'a, F, O, E>(item: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>, E>
+where
+    F: Parser<&'a str, O, E>,
+    E: ParseError<&'a str>,
+{
+    separated_list1(line_ending, item)
+}
+
+/// Parses blocks separated by one or more blank lines, e.g. `blocks(lines(u32))` for groups of
+/// numbers.
+pub fn blocks<'a, F, O, E>(block: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>, E>
+where
+    F: Parser<&'a str, O, E>,
+    E: ParseError<&'a str>,
+{
+    separated_list1(pair(line_ending, many1_count(line_ending)), block)
+}
+
+/// Parses a rectangular grid, one row per line, with `cell` parsing each character.
+pub fn grid<'a, F, O, E>(cell: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<Vec<O>>, E>
+where
+    F: Parser<&'a str, O, E>,
+    E: ParseError<&'a str>,
+{
+    verify(lines(many1(cell)), |rows: &Vec<Vec<O>>| {
+        rows.iter().all(|row| row.len() == rows[0].len())
+    })
+}
+
+/// Parses a rectangular grid of any characters.
+pub fn char_grid<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, Vec<Vec<char>>, E> {
+    grid(none_of("\r\n"))(input)
+}
+
+/// Parses a grid of `#` and `.`, e.g. walls and open space, as `true` and `false`.
+pub fn bool_grid<'a, E: ParseError<&'a str>>(
+    input: &'a str,
+) -> IResult<&'a str, Vec<Vec<bool>>, E> {
+    grid(alt((map(char('#'), |_| true), map(char('.'), |_| false))))(input)
+}
+
+#[cfg(test)]
+mod test {
+    use nom::character::complete::alpha1;
+    use nom::error::{Error, ErrorKind};
+
+    use super::*;
+
+    type Result<'a, O> = IResult<&'a str, O, Error<&'a str>>;
+
+    #[test]
+    fn test_ws() {
+        let r: Result<_> = ws(u32)(" \n 4 \n red");
+        assert_eq!(r, Ok(("red", 4)));
+        let r: Result<_> = spaced(u32)(" 4 \n red");
+        assert_eq!(r, Ok(("\n red", 4)));
+    }
+
+    #[test]
+    fn test_labelled() {
+        let r: Result<_> = labelled_id("Card")("Card   12: 1");
+        assert_eq!(r, Ok((": 1", 12)));
+        let r: Result<_> = labelled("Game", uints)("Game 3: 1 2");
+        assert_eq!(r, Ok(("", (3, vec![1, 2]))));
+        let r: Result<_> = labelled("Game", uints)("Game 3 1 2");
+        assert_eq!(r, Err(nom::Err::Error(Error::new("1 2", ErrorKind::Char))));
+    }
+
+    #[test]
+    fn test_numbers() {
+        let r: Result<_> = uints(" 41 48  6 | 83");
+        assert_eq!(r, Ok(("| 83", vec![41, 48, 6])));
+        let r: Result<_> = uints("| 83");
+        assert_eq!(r, Ok(("| 83", vec![])));
+        let r: Result<_> = ints("-3 4 -5\n6");
+        assert_eq!(r, Ok(("\n6", vec![-3, 4, -5])));
+    }
+
+    #[test]
+    fn test_separated() {
+        let r: Result<_> = separated(',', alpha1)("a, b ,c; d");
+        assert_eq!(r, Ok(("; d", vec!["a", "b", "c"])));
+        let r: Result<_> = separated(',', alpha1)("a, 1");
+        assert_eq!(r, Err(nom::Err::Failure(Error::new("1", ErrorKind::Alpha))));
+        let r: Result<Vec<&str>> = separated(',', alpha1)("1");
+        assert!(matches!(r, Err(nom::Err::Error(_))));
+    }
+
+    #[test]
+    fn test_record() {
+        let r: Result<_> = key_value("hgt:183cm pid:1");
+        assert_eq!(r, Ok((" pid:1", ("hgt", "183cm"))));
+        let r: Result<_> = record("ecl:gry pid:860033327\nhcl:#fffffd\n\nbyr:1937");
+        assert_eq!(
+            r,
+            Ok((
+                "\n\nbyr:1937",
+                vec![("ecl", "gry"), ("pid", "860033327"), ("hcl", "#fffffd")]
+            ))
+        );
+    }
+
+    #[test]
+    fn test_lines_and_blocks() {
+        let r: Result<_> = lines(u32)("1\r\n2\n3\n");
+        assert_eq!(r, Ok(("\n", vec![1, 2, 3])));
+        let r: Result<_> = blocks(lines(u32))("1\n2\n\n3\n\n\n4\n");
+        assert_eq!(r, Ok(("\n", vec![vec![1, 2], vec![3], vec![4]])));
+        let r: Result<_> = blocks(record)("a:1\nb:2\n\nc:3");
+        assert_eq!(
+            r,
+            Ok(("", vec![vec![("a", "1"), ("b", "2")], vec![("c", "3")]]))
+        );
+    }
+
+    #[test]
+    fn test_grid() {
+        let r: Result<_> = char_grid("ab\ncd\n");
+        assert_eq!(r, Ok(("\n", vec![vec!['a', 'b'], vec!['c', 'd']])));
+        let r: Result<_> = bool_grid(".#\n#.");
+        assert_eq!(r, Ok(("", vec![vec![false, true], vec![true, false]])));
+        let r: Result<_> = char_grid("ab\nc");
+        assert!(r.is_err());
+        let r: Result<_> = bool_grid(".#\n#x");
+        assert!(r.is_err());
+    }
+}